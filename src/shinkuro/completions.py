@@ -0,0 +1,69 @@
+"""Argument completion for prompts with declared choices."""
+
+from typing import Mapping, Optional
+
+from fastmcp import FastMCP
+from mcp.types import (
+    Completion,
+    CompletionArgument,
+    CompletionContext,
+    PromptReference,
+    ResourceTemplateReference,
+)
+
+from .prompts.markdown import MarkdownPrompt
+
+# Maximum number of values in one completion response, as set by MCP
+MAX_COMPLETION_VALUES = 100
+
+
+def complete_argument(
+    prompts: Mapping[str, MarkdownPrompt], name: str, argument: str, value: str
+) -> Completion:
+    """
+    Suggest choices of a prompt argument starting with the partial value.
+
+    Unknown prompts, unknown arguments and arguments without choices get an
+    empty completion instead of an error.
+
+    Args:
+        prompts: Served prompts keyed by name
+        name: Prompt name from the completion reference
+        argument: Name of the argument being completed
+        value: Partial value typed so far, matched case-insensitively
+
+    Returns:
+        Matching choices in declaration order
+    """
+    prompt = prompts.get(name)
+    choices = prompt.arg_choices.get(argument, []) if prompt else []
+    prefix = value.lower()
+    matches = [choice for choice in choices if choice.lower().startswith(prefix)]
+    return Completion(
+        values=matches[:MAX_COMPLETION_VALUES],
+        total=len(matches),
+        hasMore=len(matches) > MAX_COMPLETION_VALUES,
+    )
+
+
+def register_completions(mcp: FastMCP, prompts: Mapping[str, MarkdownPrompt]) -> None:
+    """
+    Handle completion/complete requests for prompt arguments.
+
+    Args:
+        mcp: Server to register the handler with
+        prompts: Served prompts keyed by name
+    """
+
+    # FastMCP has no completion API, register on the underlying MCP server.
+    # _mcp_server is private, which is why fastmcp is capped at a minor
+    # version in pyproject.toml, check this on every fastmcp upgrade.
+    @mcp._mcp_server.completion()
+    async def complete(
+        ref: PromptReference | ResourceTemplateReference,
+        argument: CompletionArgument,
+        context: Optional[CompletionContext],
+    ) -> Optional[Completion]:
+        if not isinstance(ref, PromptReference):
+            return None
+        return complete_argument(prompts, ref.name, argument.name, argument.value)
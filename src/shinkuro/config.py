@@ -0,0 +1,83 @@
+"""TOML config file providing defaults for command line options."""
+
+import sys
+import typer
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+from .envvars import split_folders
+from .interfaces import DefaultLogger, LoggerInterface
+
+if sys.version_info >= (3, 11):
+    import tomllib
+else:
+    import tomli as tomllib
+
+# Options that can be set in the config file
+CONFIG_KEYS = (
+    "folder",
+    "git_url",
+    "cache_dir",
+    "variable_format",
+    "auto_pull",
+    "auto_discover_args",
+    "skip_frontmatter",
+)
+
+# Config file read when --config is not given, if it exists
+DEFAULT_CONFIG_PATH = Path("~/.shinkuro/config.toml")
+
+
+def load_config(
+    path: Path, *, logger: LoggerInterface = DefaultLogger()
+) -> Dict[str, Any]:
+    """
+    Read option values from a TOML config file.
+
+    Args:
+        path: Config file path
+        logger: Logger interface for unknown keys
+
+    Returns:
+        Option values keyed by parameter name
+
+    Raises:
+        OSError: If the file cannot be read
+        ValueError: If the file is not valid TOML
+    """
+    with open(path, "rb") as f:
+        try:
+            data = tomllib.load(f)
+        except tomllib.TOMLDecodeError as e:
+            raise ValueError(f"Invalid TOML in config file {path}: {e}") from e
+
+    config: Dict[str, Any] = {}
+    for key, value in data.items():
+        if key not in CONFIG_KEYS:
+            logger.warning(f"unknown key '{key}' in config file {path}, ignoring")
+            continue
+        if key == "folder" and isinstance(value, str):
+            # --folder is repeatable, a string lists folders like FOLDER does
+            value = split_folders(value)
+        config[key] = value
+    return config
+
+
+def config_callback(
+    ctx: typer.Context, param: typer.CallbackParam, value: Optional[str]
+) -> Optional[str]:
+    """
+    Load the config file as defaults of the other options.
+
+    The option is eager, so this runs before other options are resolved and
+    command line flags and environment variables still take precedence.
+    """
+    path = Path(value or DEFAULT_CONFIG_PATH).expanduser()
+    if not value and not path.exists():
+        return value
+    try:
+        config = load_config(path)
+    except (OSError, ValueError) as e:
+        raise typer.BadParameter(str(e)) from e
+    ctx.default_map = {**config, **(ctx.default_map or {})}
+    return value
@@ -0,0 +1,366 @@
+"""Diagnostic checks explaining why prompts do or do not load."""
+
+import os
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Callable, Iterable, Optional
+
+from .file.scan import (
+    iter_markdown_files,
+    parse_markdown_file,
+    scan_markdown_folders,
+)
+from .formatters import FormatterInterface, resolve_formatter
+from .interfaces import DefaultFileSystem, FileSystemInterface
+from .model import PromptData
+from .prompts.markdown import MarkdownPrompt
+from .remote.git import get_local_cache_path
+
+
+@dataclass
+class Check:
+    """Result of a single diagnostic check.
+
+    Attributes:
+        name: Short name of the check
+        passed: Whether the check passed
+        message: What was found
+        hint: Suggested remediation when the check failed
+    """
+
+    name: str
+    passed: bool
+    message: str
+    hint: Optional[str] = None
+
+
+class _CollectingLogger:
+    """Logger collecting errors and warnings so they can be attached to a check."""
+
+    def __init__(self):
+        self.warnings: list[str] = []
+
+    def error(self, message: str) -> None:
+        self.warnings.append(message)
+
+    def warning(self, message: str) -> None:
+        self.warnings.append(message)
+
+    def info(self, message: str) -> None:
+        pass
+
+    def debug(self, message: str) -> None:
+        pass
+
+
+def _is_writable(path: Path) -> bool:
+    """Check whether a directory exists and is writable, or could be created."""
+    for candidate in [path, *path.parents]:
+        if candidate.exists():
+            return candidate.is_dir() and os.access(candidate, os.W_OK)
+    return False
+
+
+def run_checks(
+    folders: list[str],
+    git_url: Optional[str],
+    cache_dir: Path,
+    skip_frontmatter: bool,
+    formatter: FormatterInterface,
+    auto_discover_args: bool,
+    *,
+    git_ref: Optional[str] = None,
+    include_hidden: bool = False,
+    include: Optional[list[str]] = None,
+    namespace_separator: Optional[str] = None,
+    strict_frontmatter: bool = False,
+    fs: FileSystemInterface = DefaultFileSystem(),
+) -> list[Check]:
+    """
+    Diagnose the prompt source configuration without cloning or serving.
+
+    Args:
+        folders: Local folders, or subfolders within the git repo
+        git_url: Git repository URL
+        cache_dir: Directory to cache remote repositories
+        skip_frontmatter: Whether to skip frontmatter processing
+        formatter: Formatter used to validate prompt templates
+        auto_discover_args: Whether template variables are auto-discovered
+        git_ref: Branch, tag or commit of the git repository
+        include_hidden: Whether hidden files and directories are loaded
+        include: Gitignore-style patterns files must match, if given
+        namespace_separator: Joins directories into default prompt names, if set
+        strict_frontmatter: Whether invalid leading JSON frontmatter fails
+        fs: File system interface for file operations
+
+    Returns:
+        Checks in the order they were run
+    """
+    checks: list[Check] = []
+
+    if git_url:
+        try:
+            repo_path = get_local_cache_path(git_url, cache_dir, git_ref)
+        except ValueError as e:
+            checks.append(
+                Check(
+                    "git url",
+                    False,
+                    str(e),
+                    "use a URL like https://github.com/owner/repo.git or git@github.com:owner/repo.git",
+                )
+            )
+            return checks
+        checks.append(Check("git url", True, f"resolves to cache path {repo_path}"))
+
+        if _is_writable(cache_dir):
+            checks.append(Check("cache dir", True, f"{cache_dir} is writable"))
+        else:
+            checks.append(
+                Check(
+                    "cache dir",
+                    False,
+                    f"{cache_dir} is not writable",
+                    "set --cache-dir to a writable directory",
+                )
+            )
+
+        if not fs.exists(repo_path):
+            checks.append(
+                Check(
+                    "repository",
+                    True,
+                    f"not cloned yet, it will be cloned into {repo_path} on startup",
+                )
+            )
+            return checks
+        folder_paths = [repo_path / folder for folder in folders] or [repo_path]
+    elif folders:
+        folder_paths = [Path(folder) for folder in folders]
+    else:
+        checks.append(
+            Check(
+                "configuration",
+                False,
+                "neither folder nor git-url is provided",
+                "set --folder (FOLDER) or --git-url (GIT_URL)",
+            )
+        )
+        return checks
+
+    scanned: list[Path] = []
+    for folder_path in folder_paths:
+        folder_checks = _check_folder(
+            folder_path,
+            skip_frontmatter,
+            formatter,
+            auto_discover_args,
+            include_hidden=include_hidden,
+            include=include,
+            namespace_separator=namespace_separator,
+            strict_frontmatter=strict_frontmatter,
+            fs=fs,
+        )
+        checks += folder_checks
+        if folder_checks[-1].name not in ("folder", "markdown files"):
+            scanned.append(folder_path)
+
+    if scanned:
+        checks.append(
+            _check_duplicate_names(
+                scanned,
+                skip_frontmatter,
+                include_hidden=include_hidden,
+                include=include,
+                namespace_separator=namespace_separator,
+                strict_frontmatter=strict_frontmatter,
+                fs=fs,
+            )
+        )
+    return checks
+
+
+def _check_folder(
+    folder_path: Path,
+    skip_frontmatter: bool,
+    formatter: FormatterInterface,
+    auto_discover_args: bool,
+    *,
+    include_hidden: bool,
+    include: Optional[list[str]],
+    namespace_separator: Optional[str],
+    strict_frontmatter: bool,
+    fs: FileSystemInterface,
+) -> list[Check]:
+    """Check that a folder is readable and try to load each of its files."""
+    if not fs.exists(folder_path) or not fs.is_dir(folder_path):
+        return [
+            Check(
+                "folder",
+                False,
+                f"'{folder_path}' does not exist or is not a directory",
+                "check the --folder path; inside a git repo it is relative to the repository root",
+            )
+        ]
+
+    try:
+        # Same selection as the server, skipped files are not diagnosed
+        md_files = sorted(
+            iter_markdown_files(
+                folder_path,
+                include_hidden=include_hidden,
+                include=include,
+                fs=fs,
+                logger=_CollectingLogger(),
+            )
+        )
+    except OSError as e:
+        return [
+            Check(
+                "folder",
+                False,
+                f"'{folder_path}' is not readable: {e}",
+                "check the folder permissions",
+            )
+        ]
+    checks = [Check("folder", True, f"'{folder_path}' exists and is readable")]
+
+    if not md_files:
+        checks.append(
+            Check(
+                "markdown files",
+                False,
+                f"no markdown files found in '{folder_path}'",
+                "prompts must be files with the .md extension",
+            )
+        )
+        return checks
+    checks.append(Check("markdown files", True, f"found {len(md_files)} file(s)"))
+
+    for md_file in md_files:
+        logger = _CollectingLogger()
+        try:
+            prompt_data = parse_markdown_file(
+                md_file,
+                folder_path,
+                skip_frontmatter,
+                namespace_separator=namespace_separator,
+                strict_frontmatter=strict_frontmatter,
+                fs=fs,
+                logger=logger,
+            )
+            MarkdownPrompt.from_prompt_data(
+                prompt_data,
+                resolve_formatter(prompt_data, formatter),
+                auto_discover_args,
+            )
+        except Exception as e:
+            checks.append(
+                Check(
+                    str(md_file.relative_to(folder_path)),
+                    False,
+                    str(e),
+                    "fix the frontmatter or template variables in this file",
+                )
+            )
+            continue
+        message = f"loads as prompt '{prompt_data.name}'"
+        if logger.warnings:
+            message += f" with warnings: {'; '.join(logger.warnings)}"
+        checks.append(Check(str(md_file.relative_to(folder_path)), True, message))
+
+    return checks
+
+
+def _check_duplicate_names(
+    folder_paths: list[Path],
+    skip_frontmatter: bool,
+    *,
+    include_hidden: bool,
+    include: Optional[list[str]],
+    namespace_separator: Optional[str],
+    strict_frontmatter: bool,
+    fs: FileSystemInterface,
+) -> Check:
+    """Report prompt names shared by files in one folder, as the scan does."""
+    duplicates: list[str] = []
+    logger = _CollectingLogger()
+    scan_markdown_folders(
+        folder_paths,
+        skip_frontmatter,
+        include_hidden=include_hidden,
+        namespace_separator=namespace_separator,
+        strict_frontmatter=strict_frontmatter,
+        include=include,
+        duplicates=duplicates,
+        fs=fs,
+        logger=logger,
+    )
+    if not duplicates:
+        return Check("prompt names", True, "no duplicate prompt names")
+    return Check(
+        "prompt names",
+        False,
+        "; ".join(
+            warning
+            for warning in logger.warnings
+            if warning.startswith("duplicate prompt name")
+        ),
+        "set a unique name in the frontmatter, or use --namespace-by-dir",
+    )
+
+
+def check_prompts(
+    prompts: Iterable[PromptData],
+    failures: Iterable[tuple[Path, str]],
+    build: Callable[[PromptData], MarkdownPrompt],
+) -> list[Check]:
+    """
+    Validate scanned prompts the way the server would build them.
+
+    Args:
+        prompts: Prompt data that was scanned successfully
+        failures: Files that could not be read or parsed, with the reason
+        build: Builds the served prompt, raising on invalid prompts
+
+    Returns:
+        One check per file, failed files first
+    """
+    checks = [
+        Check(str(path), False, reason, "fix the file or its frontmatter")
+        for path, reason in failures
+    ]
+    for prompt_data in prompts:
+        name = str(prompt_data.path or prompt_data.name)
+        try:
+            build(prompt_data)
+        except ValueError as e:
+            checks.append(
+                Check(
+                    name,
+                    False,
+                    str(e),
+                    "fix the frontmatter or template variables in this file",
+                )
+            )
+            continue
+        checks.append(Check(name, True, f"loads as prompt '{prompt_data.name}'"))
+    return checks
+
+
+def format_report(checks: list[Check]) -> str:
+    """Format checks as a human readable pass/fail report."""
+    lines = []
+    for check in checks:
+        status = "PASS" if check.passed else "FAIL"
+        lines.append(f"[{status}] {check.name}: {check.message}")
+        if check.hint and not check.passed:
+            lines.append(f"       hint: {check.hint}")
+
+    failed = sum(1 for check in checks if not check.passed)
+    lines.append(
+        f"{len(checks) - failed} passed, {failed} failed"
+        if failed
+        else f"all {len(checks)} checks passed"
+    )
+    return "\n".join(lines)
@@ -0,0 +1,47 @@
+"""Parsing of environment variables for repeatable command line options."""
+
+import os
+import re
+from typing import List, Sequence
+
+import click
+
+
+class EnvVarLines(click.types.StringParamType):
+    """
+    Text option whose environment variable holds one value per line.
+
+    Click splits the environment variable of a repeatable option on any
+    whitespace, which breaks paths, regexes and overrides containing spaces.
+    """
+
+    def split_envvar_value(self, rv: str) -> Sequence[str]:
+        return [line for line in rv.splitlines() if line]
+
+
+def split_folders(value: str) -> List[str]:
+    """
+    Split a FOLDER value listing several folders.
+
+    Folders may be separated by newlines, commas or the platform path
+    separator, as in FOLDER=team,personal or FOLDER=team:personal on unix.
+    """
+    parts = re.split(f"[\n,{re.escape(os.pathsep)}]", value)
+    return [part.strip() for part in parts if part.strip()]
+
+
+class EnvVarFolders(EnvVarLines):
+    """
+    Folder option whose environment variable lists folders.
+
+    Only the environment variable is split, a --folder value is used as is
+    so a directory with a comma in its name can still be given.
+    """
+
+    def split_envvar_value(self, rv: str) -> Sequence[str]:
+        return split_folders(rv)
+
+
+# Shared instances for typer.Option(click_type=...)
+ENV_VAR_LINES = EnvVarLines()
+ENV_VAR_FOLDERS = EnvVarFolders()
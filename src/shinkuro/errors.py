@@ -0,0 +1,135 @@
+"""Structured errors for prompt validation and rendering."""
+
+from typing import Any, Iterable, Optional
+
+from fastmcp.exceptions import NotFoundError
+from fastmcp.server.middleware import CallNext, Middleware, MiddlewareContext
+from mcp.shared.exceptions import McpError
+from mcp.types import INVALID_PARAMS, ErrorData
+
+
+class PromptError(ValueError):
+    """Base class for errors raised while building or rendering a prompt."""
+
+    code: int = INVALID_PARAMS
+
+    def to_mcp_error(self) -> McpError:
+        """Convert to an MCP error carrying the matching JSON-RPC error code."""
+        return McpError(ErrorData(code=self.code, message=str(self)))
+
+
+class UnknownPrompt(PromptError):
+    """No prompt has the requested name."""
+
+    def __init__(self, name: str):
+        self.name = name
+        super().__init__(f"Unknown prompt: {name}")
+
+
+class InvalidArgumentName(PromptError):
+    """Argument name is not a valid identifier."""
+
+    def __init__(self, name: str):
+        self.name = name
+        super().__init__(f"Argument name '{name}' contains invalid characters")
+
+
+class ArgumentMismatch(PromptError):
+    """Variables referenced in content differ from declared arguments."""
+
+    def __init__(self, discovered: set[str], provided: set[str]):
+        self.discovered = discovered
+        self.provided = provided
+        super().__init__(
+            f"Content arguments {discovered} don't match provided arguments {provided}"
+        )
+
+
+class ArgumentsNotAllowed(PromptError):
+    """Declared arguments were given while auto-discovery is enabled."""
+
+    def __init__(self):
+        super().__init__(
+            "prompt_data.arguments must be empty when auto_discover_args is enabled"
+        )
+
+
+class MissingRequired(PromptError):
+    """Required arguments were not provided at render time."""
+
+    def __init__(self, names: Iterable[str]):
+        self.names = sorted(names)
+        super().__init__(f"Missing required arguments: {set(self.names)}")
+
+
+class UnexpectedArguments(PromptError):
+    """Provided arguments are not declared by the prompt."""
+
+    def __init__(self, names: Iterable[str]):
+        self.names = sorted(names)
+        super().__init__(f"Unexpected arguments: {set(self.names)}")
+
+
+class InvalidArgumentType(PromptError):
+    """An argument value does not match the declared argument type."""
+
+    def __init__(self, name: str, expected: str, value: str):
+        self.name = name
+        self.expected = expected
+        super().__init__(f"Argument '{name}' must be a {expected}, got '{value}'")
+
+
+class ArgumentsTooLarge(PromptError):
+    """Combined size of provided argument values exceeds the limit."""
+
+    def __init__(self, total: int, limit: int):
+        self.total = total
+        self.limit = limit
+        super().__init__(
+            f"Total argument size {total} bytes exceeds the limit of {limit} bytes"
+        )
+
+
+class CyclicReference(PromptError):
+    """Argument defaults reference each other without converging."""
+
+    def __init__(self, names: Iterable[str], passes: int):
+        self.names = sorted(names)
+        self.passes = passes
+        super().__init__(
+            f"Argument references not resolved after {passes} passes, "
+            f"possible cycle: {self.names}"
+        )
+
+
+class PromptErrorMiddleware(Middleware):
+    """
+    Send prompt errors to clients as JSON-RPC errors carrying their code.
+
+    FastMCP wraps exceptions raised while rendering in its own error, which
+    reaches the client without a code, so the original error is unwrapped.
+    Unknown prompt names are reported the same way.
+    """
+
+    async def on_get_prompt(
+        self, context: MiddlewareContext, call_next: CallNext
+    ) -> Any:
+        try:
+            return await call_next(context)
+        except NotFoundError as e:
+            raise UnknownPrompt(context.message.name).to_mcp_error() from e
+        except Exception as e:
+            error = _find_prompt_error(e)
+            if error is None:
+                raise
+            raise error.to_mcp_error() from e
+
+
+def _find_prompt_error(error: BaseException) -> Optional[PromptError]:
+    """Find a PromptError among the causes of an exception."""
+    current: Optional[BaseException] = error
+    while current is not None:
+        if isinstance(current, PromptError):
+            return current
+        current = current.__cause__
+    return None
@@ -0,0 +1,69 @@
+"""Classification of prompt file read failures."""
+
+import os
+import stat
+from pathlib import Path
+from typing import Optional
+
+PERMISSION_DENIED = "permission denied"
+NOT_FOUND = "not found"
+BROKEN_SYMLINK = "broken symlink"
+IO_ERROR = "io error"
+
+
+def _error_path(error: OSError) -> Optional[Path]:
+    return Path(error.filename) if error.filename else None
+
+
+def classify_read_error(error: OSError) -> str:
+    """
+    Classify an error raised while reading a prompt file.
+
+    Args:
+        error: Error raised by a file system operation
+
+    Returns:
+        One of PERMISSION_DENIED, NOT_FOUND, BROKEN_SYMLINK or IO_ERROR
+    """
+    if isinstance(error, PermissionError):
+        return PERMISSION_DENIED
+    if isinstance(error, FileNotFoundError):
+        path = _error_path(error)
+        if path is not None and path.is_symlink():
+            return BROKEN_SYMLINK
+        return NOT_FOUND
+    return IO_ERROR
+
+
+def describe_read_error(error: OSError) -> str:
+    """
+    Describe a read error with enough detail to fix it.
+
+    Permission errors include the file owner and mode on unix, broken
+    symlinks include the missing target.
+
+    Args:
+        error: Error raised by a file system operation
+
+    Returns:
+        Human readable description starting with the error class
+    """
+    kind = classify_read_error(error)
+    path = _error_path(error)
+
+    if kind == PERMISSION_DENIED:
+        if path is None or not hasattr(os, "getuid"):
+            return kind
+        try:
+            st = path.stat()
+        except OSError:
+            return kind
+        return (
+            f"{kind} (owner uid {st.st_uid}, mode {stat.filemode(st.st_mode)}, "
+            f"running as uid {os.getuid()})"
+        )
+    if kind == BROKEN_SYMLINK and path is not None:
+        return f"{kind} (target {os.readlink(path)} does not exist)"
+    if kind == NOT_FOUND:
+        return f"{kind} (file disappeared after the folder was scanned)"
+    return f"{kind}: {error.strerror or error}"
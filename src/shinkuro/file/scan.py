@@ -1,9 +1,27 @@
 """Local file-based prompt loader."""
 
+import json
+import re
+import sys
+from collections import Counter
+from fnmatch import fnmatch
 import frontmatter
+import yaml
+from frontmatter.default_handlers import BaseHandler, JSONHandler, YAMLHandler
 from pathlib import Path
-from typing import Iterator, Optional, Any
-from ..model import Argument, PromptData
+from pathspec import GitIgnoreSpec
+from typing import Iterable, Iterator, Optional, Any
+from ..model import (
+    ROLES,
+    Argument,
+    ArgumentType,
+    FormatterType,
+    PromptData,
+    role_marker,
+)
+from ..postprocessors import parse_post_processors
+from ..timing import TimingCollector
+from .read_errors import classify_read_error, describe_read_error
 from ..interfaces import (
     FileSystemInterface,
     DefaultFileSystem,
@@ -11,6 +29,151 @@ from ..interfaces import (
     DefaultLogger,
 )
 
+if sys.version_info >= (3, 11):
+    import tomllib
+else:
+    import tomli as tomllib
+
+
+# File name patterns left behind by sync tools and editors
+SYNC_ARTIFACT_PATTERNS = ["*.sync-conflict-*", "*~", "#*#", ".#*"]
+
+_SYNC_CONFLICT_SUFFIX = re.compile(r"\.sync-conflict-[^.]*")
+
+# File at the scan folder root listing gitignore-style patterns of files to skip
+IGNORE_FILE = ".shinkuroignore"
+
+
+def _is_hidden(md_file: Path, folder: Path) -> bool:
+    """Check if the file or any directory below the folder is a dot-entry."""
+    return any(part.startswith(".") for part in md_file.relative_to(folder).parts)
+
+
+def _is_sync_artifact(md_file: Path) -> bool:
+    """Check if the file name matches a known sync or backup artifact pattern."""
+    return any(fnmatch(md_file.name, pattern) for pattern in SYNC_ARTIFACT_PATTERNS)
+
+
+def _load_ignore_spec(
+    folder: Path, *, fs: FileSystemInterface, logger: LoggerInterface
+) -> Optional[GitIgnoreSpec]:
+    """Read the ignore file of a scan folder, if it has one."""
+    ignore_file = folder / IGNORE_FILE
+    if not fs.exists(ignore_file):
+        return None
+    try:
+        return GitIgnoreSpec.from_lines(fs.read_text(ignore_file).splitlines())
+    except OSError as e:
+        logger.error(f"failed to read {ignore_file}: {describe_read_error(e)}")
+        return None
+
+
+def _describe_sync_artifact(md_file: Path, fs: FileSystemInterface) -> str:
+    """Describe a skipped sync artifact, noting conflict copies that are harmless."""
+    primary = md_file.with_name(_SYNC_CONFLICT_SUFFIX.sub("", md_file.name))
+    if primary != md_file and fs.exists(primary):
+        try:
+            if fs.read_text(primary) == fs.read_text(md_file):
+                return f"sync artifact, identical to {primary.name} (harmless)"
+        except Exception:
+            pass
+    return "sync artifact"
+
+
+# Scalar tags whose source text is kept for an argument default
+_TEXT_DEFAULT_TAGS = {
+    "tag:yaml.org,2002:bool",
+    "tag:yaml.org,2002:int",
+    "tag:yaml.org,2002:float",
+}
+
+
+class _FrontmatterLoader(yaml.SafeLoader):
+    """YAML loader that only treats true/false as booleans, like YAML 1.2."""
+
+    def construct_mapping(
+        self, node: yaml.MappingNode, deep: bool = False
+    ) -> dict[Any, Any]:
+        mapping = super().construct_mapping(node, deep=deep)
+        # A default is substituted as text, so keep it as written rather
+        # than str() of the parsed value (true, not True; 1.50, not 1.5)
+        for key_node, value_node in node.value:
+            if (
+                key_node.value == "default"
+                and isinstance(value_node, yaml.ScalarNode)
+                and value_node.tag in _TEXT_DEFAULT_TAGS
+            ):
+                mapping["default"] = value_node.value
+        return mapping
+
+
+# Drop YAML 1.1 truthy values (yes/no/on/off) so they stay literal strings
+_FrontmatterLoader.yaml_implicit_resolvers = {
+    first: [
+        (tag, regexp) for tag, regexp in resolvers if tag != "tag:yaml.org,2002:bool"
+    ]
+    for first, resolvers in yaml.SafeLoader.yaml_implicit_resolvers.items()
+}
+_FrontmatterLoader.add_implicit_resolver(
+    "tag:yaml.org,2002:bool",
+    re.compile(r"^(?:true|True|TRUE|false|False|FALSE)$"),
+    list("tTfF"),
+)
+
+
+class _YAMLHandler(YAMLHandler):
+    """Frontmatter YAML handler using the literal-preserving loader."""
+
+    def load(self, fm: str, **kwargs: object) -> object:
+        return yaml.load(fm, Loader=_FrontmatterLoader)
+
+
+class _TOMLHandler(BaseHandler):
+    """Frontmatter handler for TOML delimited by +++."""
+
+    FM_BOUNDARY = re.compile(r"^\+{3,}\s*$", re.MULTILINE)
+    START_DELIMITER = END_DELIMITER = "+++"
+
+    def load(self, fm: str, **kwargs: object) -> object:
+        return tomllib.loads(fm)
+
+
+class _DelimitedJSONHandler(BaseHandler):
+    """Frontmatter handler for a JSON object delimited by ;;;."""
+
+    FM_BOUNDARY = re.compile(r"^;{3,}\s*$", re.MULTILINE)
+    START_DELIMITER = END_DELIMITER = ";;;"
+
+    def load(self, fm: str, **kwargs: object) -> object:
+        return json.loads(fm)
+
+
+class _LeadingJSONHandler(JSONHandler):
+    """
+    Frontmatter handler for a leading JSON object.
+
+    The handler only matches when the opening { line has a closing } line,
+    a body merely starting with a single { is left alone.
+    """
+
+    def detect(self, text: str) -> bool:
+        if not super().detect(text):
+            return False
+        try:
+            self.split(text)
+        except ValueError:
+            return False
+        return True
+
+
+# Frontmatter format is chosen by the opening delimiter
+_FRONTMATTER_HANDLERS = [
+    _YAMLHandler(),
+    _TOMLHandler(),
+    _DelimitedJSONHandler(),
+    _LeadingJSONHandler(),
+]
+
 
 def _extract_string_field(
     metadata: dict,
@@ -33,6 +196,35 @@ def _extract_string_field(
         return str(value)
 
 
+def _extract_description(
+    metadata: dict,
+    default: str,
+    file_path: Path,
+    *,
+    logger: LoggerInterface,
+) -> tuple[str, dict[str, str]]:
+    """Extract the description, which may be a map of locale to text."""
+    value = metadata.get("description")
+    if not isinstance(value, dict):
+        description = _extract_string_field(
+            metadata, "description", default, file_path, logger=logger
+        )
+        return description, {}
+
+    descriptions = {}
+    for locale, text in value.items():
+        if not isinstance(text, str):
+            logger.warning(
+                f"'description' for locale '{locale}' in {file_path} is not a string, converting to string"
+            )
+            text = str(text)
+        descriptions[str(locale)] = text
+
+    # Fallback when the requested locale is missing: English, then the first entry
+    fallback = descriptions.get("en") or next(iter(descriptions.values()), default)
+    return fallback, descriptions
+
+
 def _parse_argument(
     arg_data: Any, file_path: Path, *, logger: LoggerInterface
 ) -> Optional[Argument]:
@@ -64,13 +256,51 @@ def _parse_argument(
 
     # Handle default field
     arg_default = arg_data.get("default")
-    if arg_default is not None and not isinstance(arg_default, str):
+    if isinstance(arg_default, (bool, int, float)):
+        # TOML and JSON scalars, written like YAML keeps them (true, not True)
+        arg_default = json.dumps(arg_default)
+    elif arg_default is not None and not isinstance(arg_default, str):
         logger.warning(
             f"argument 'default' field in {file_path} is not a string, converting to string"
         )
         arg_default = str(arg_default)
 
-    return Argument(name=arg_name, description=arg_description, default=arg_default)
+    # Handle choices field, enum is accepted as in JSON Schema
+    choices_key = "enum" if "enum" in arg_data else "choices"
+    if "enum" in arg_data and "choices" in arg_data:
+        logger.warning(
+            f"argument has both 'choices' and 'enum' fields in {file_path}, "
+            "using 'choices'"
+        )
+        choices_key = "choices"
+    arg_choices = arg_data.get(choices_key, [])
+    if arg_choices is None:
+        arg_choices = []
+    elif not isinstance(arg_choices, list):
+        logger.warning(
+            f"argument '{choices_key}' field in {file_path} is not a list, ignoring"
+        )
+        arg_choices = []
+
+    # Handle type field, values are validated at render time
+    arg_type = ArgumentType.STRING
+    if arg_data.get("type") is not None:
+        try:
+            arg_type = ArgumentType(arg_data["type"])
+        except ValueError:
+            types = ", ".join(t.value for t in ArgumentType)
+            logger.warning(
+                f"argument 'type' field in {file_path} is not one of {types}, "
+                "using 'string'"
+            )
+
+    return Argument(
+        name=arg_name,
+        description=arg_description,
+        default=arg_default,
+        choices=[str(choice) for choice in arg_choices],
+        type=arg_type,
+    )
 
 
 def _parse_arguments(
@@ -91,59 +321,273 @@ def _parse_arguments(
     return arguments
 
 
+def _parse_post(
+    metadata: dict, file_path: Path, *, logger: LoggerInterface
+) -> list[str]:
+    """Parse and validate the post-processor list from frontmatter metadata."""
+    post = metadata.get("post", [])
+    if post is None:
+        return []
+    if isinstance(post, str):
+        post = [post]
+    if not isinstance(post, list):
+        raise ValueError("'post' field must be a list of post-processors")
+
+    specs = []
+    for item in post:
+        if not isinstance(item, str):
+            logger.warning(
+                f"'post' item in {file_path} is not a string, converting to string"
+            )
+            item = str(item)
+        specs.append(item)
+
+    # Unknown names and bad parameters are load-time errors
+    parse_post_processors(specs)
+    return specs
+
+
+def _parse_variable_format(
+    metadata: dict, file_path: Path, *, logger: LoggerInterface
+) -> Optional[FormatterType]:
+    """Parse the per-file variable format, None keeps the global format."""
+    value = metadata.get("variable_format")
+    if value is None:
+        return None
+    try:
+        return FormatterType(value)
+    except ValueError:
+        choices = ", ".join(f.value for f in FormatterType)
+        logger.warning(
+            f"'variable_format' field in {file_path} is not one of {choices}, "
+            "using the global variable format"
+        )
+        return None
+
+
+def _parse_messages(metadata: dict, body: str) -> Optional[str]:
+    """Convert the frontmatter messages list into content with role markers."""
+    messages = metadata.get("messages")
+    if messages is None:
+        return None
+    if not isinstance(messages, list) or not messages:
+        raise ValueError("'messages' field must be a non-empty list")
+    if body.strip():
+        raise ValueError("'messages' field cannot be combined with a markdown body")
+
+    sections = []
+    for index, item in enumerate(messages):
+        if not isinstance(item, dict) or not isinstance(item.get("content"), str):
+            raise ValueError(f"messages[{index}] must have a string 'content' field")
+        role = item.get("role", "user")
+        if role not in ROLES:
+            raise ValueError(
+                f"Invalid role '{role}' in messages[{index}], "
+                f"expected one of: {', '.join(ROLES)}"
+            )
+        sections.append(f"{role_marker(role)}\n{item['content']}")
+    return "\n\n".join(sections)
+
+
+def _default_name(
+    md_file: Path, folder: Path, namespace_separator: Optional[str]
+) -> str:
+    """Derive the prompt name from the file, prefixed with its directories."""
+    if namespace_separator is None:
+        return md_file.stem
+    directories = md_file.relative_to(folder).parent.parts
+    return namespace_separator.join([*directories, md_file.stem])
+
+
 def _parse_markdown_file(
     md_file: Path,
     folder: Path,
     content: str,
     skip_frontmatter: bool,
     *,
+    namespace_separator: Optional[str] = None,
+    strict_frontmatter: bool = False,
     logger: LoggerInterface,
 ) -> PromptData:
     """Parse a single markdown file into PromptData."""
+    # Files saved on Windows may start with a BOM and use CRLF line endings,
+    # which would hide the frontmatter delimiters
+    content = content.removeprefix("\ufeff").replace("\r\n", "\n")
+    default_name = _default_name(md_file, folder, namespace_separator)
     default_description = f"Prompt from {md_file.relative_to(folder)}"
 
     if skip_frontmatter:
         # Skip frontmatter processing, use file content as-is
         return PromptData(
-            name=md_file.stem,
+            name=default_name,
             title=md_file.stem,
             description=default_description,
             arguments=[],
             content=content,
+            path=md_file,
         )
 
-    post = frontmatter.loads(content)
+    stripped = content.strip()
+    handler = next((h for h in _FRONTMATTER_HANDLERS if h.detect(stripped)), None)
+    try:
+        # Without a handler frontmatter would detect one itself, including JSON
+        post = (
+            frontmatter.loads(content, handler=handler)
+            if handler
+            else frontmatter.Post(stripped)
+        )
+    except ValueError as e:
+        # A body can start with a JSON-like block, e.g. a request template
+        if strict_frontmatter or not isinstance(handler, _LeadingJSONHandler):
+            raise
+        logger.warning(
+            f"invalid JSON frontmatter in {md_file}: {e}, "
+            "using the whole file as prompt content"
+        )
+        post = frontmatter.Post(stripped)
 
     name = _extract_string_field(
-        post.metadata, "name", md_file.stem, md_file, logger=logger
+        post.metadata, "name", default_name, md_file, logger=logger
     )
     title = _extract_string_field(
         post.metadata, "title", md_file.stem, md_file, logger=logger
     )
-    description = _extract_string_field(
-        post.metadata,
-        "description",
-        default_description,
+    description, descriptions = _extract_description(
+        post.metadata, default_description, md_file, logger=logger
+    )
+    arguments = _parse_arguments(post.metadata, md_file, logger=logger)
+    post_processors = _parse_post(post.metadata, md_file, logger=logger)
+    messages = _parse_messages(post.metadata, post.content)
+    variable_format = _parse_variable_format(post.metadata, md_file, logger=logger)
+
+    return PromptData(
+        name,
+        title,
+        description,
+        arguments,
+        post.content if messages is None else messages,
         md_file,
+        post_processors,
+        descriptions,
+        variable_format=variable_format,
+    )
+
+
+def parse_markdown_file(
+    md_file: Path,
+    folder: Path,
+    skip_frontmatter: bool,
+    *,
+    namespace_separator: Optional[str] = None,
+    strict_frontmatter: bool = False,
+    fs: FileSystemInterface = DefaultFileSystem(),
+    logger: LoggerInterface = DefaultLogger(),
+) -> PromptData:
+    """
+    Read and parse a single markdown file.
+
+    Args:
+        md_file: Path to the markdown file
+        folder: Folder the file was scanned from
+        skip_frontmatter: Whether to skip frontmatter processing
+        namespace_separator: Joins directories into the default name, if set
+        strict_frontmatter: Whether invalid leading JSON frontmatter fails the
+            file instead of being used as prompt content
+        fs: File system interface for file operations
+        logger: Logger interface for warning messages
+
+    Returns:
+        PromptData for the file
+    """
+    content = fs.read_text(md_file)
+    return _parse_markdown_file(
+        md_file,
+        folder,
+        content,
+        skip_frontmatter,
+        namespace_separator=namespace_separator,
+        strict_frontmatter=strict_frontmatter,
         logger=logger,
     )
-    arguments = _parse_arguments(post.metadata, md_file, logger=logger)
 
-    return PromptData(name, title, description, arguments, post.content)
+
+def iter_markdown_files(
+    folder: Path,
+    *,
+    include_hidden: bool = False,
+    include: Optional[list[str]] = None,
+    fs: FileSystemInterface = DefaultFileSystem(),
+    logger: LoggerInterface = DefaultLogger(),
+) -> Iterator[Path]:
+    """
+    Find the markdown files of an existing folder that are loaded as prompts.
+
+    Dot-files and files under dot-directories are skipped unless
+    include_hidden is set. Sync conflict copies and editor backups are always
+    skipped and reported. Files matching a pattern in the .shinkuroignore file
+    at the folder root are skipped, and with include patterns so are files
+    matching none of them.
+
+    Args:
+        folder: Folder to search recursively
+        include_hidden: Whether to load hidden files and directories
+        include: Gitignore-style patterns relative to the folder, files must
+            match at least one when given
+        fs: File system interface for file operations
+        logger: Logger interface for skipped files
+
+    Yields:
+        Path of each markdown file to load
+    """
+    ignore_spec = _load_ignore_spec(folder, fs=fs, logger=logger)
+    include_spec = GitIgnoreSpec.from_lines(include) if include else None
+    for md_file in fs.glob_markdown(folder):
+        if not include_hidden and _is_hidden(md_file, folder):
+            continue
+        relative = md_file.relative_to(folder).as_posix()
+        if ignore_spec and ignore_spec.match_file(relative):
+            logger.debug(f"skipped {md_file}: matched by {IGNORE_FILE}")
+            continue
+        if include_spec and not include_spec.match_file(relative):
+            logger.debug(f"skipped {md_file}: not matched by --include")
+            continue
+        if _is_sync_artifact(md_file):
+            logger.warning(f"skipped {md_file}: {_describe_sync_artifact(md_file, fs)}")
+            continue
+        yield md_file
 
 
 def scan_markdown_files(
     folder: Path,
     skip_frontmatter: bool,
     *,
+    include_hidden: bool = False,
+    namespace_separator: Optional[str] = None,
+    strict_frontmatter: bool = False,
+    include: Optional[list[str]] = None,
+    read_errors: Optional[Counter[str]] = None,
+    failures: Optional[list[tuple[Path, str]]] = None,
+    timings: Optional[TimingCollector] = None,
     fs: FileSystemInterface = DefaultFileSystem(),
     logger: LoggerInterface = DefaultLogger(),
 ) -> Iterator[PromptData]:
     """
     Scan folder recursively for markdown files.
 
+    Files are selected by iter_markdown_files.
+
     Args:
         folder_path: Path to folder to scan
+        include_hidden: Whether to load hidden files and directories
+        namespace_separator: Joins directories into default prompt names, if set
+        strict_frontmatter: Whether files with invalid leading JSON
+            frontmatter fail to load instead of using it as prompt content
+        include: Gitignore-style patterns relative to the folder, files must
+            match at least one when given
+        read_errors: Counter updated with the class of each file read failure
+        failures: List extended with each file that failed to load and why
+        timings: Collector recording the walk and file reads as the scan
+            phase and frontmatter parsing as the parse phase
         fs: File system interface for file operations
         logger: Logger interface for warning messages
 
@@ -156,13 +600,131 @@ def scan_markdown_files(
         )
         return
 
-    for md_file in fs.glob_markdown(folder):
-        try:
-            content = fs.read_text(md_file)
-            prompt_data = _parse_markdown_file(
-                md_file, folder, content, skip_frontmatter, logger=logger
+    timings = timings or TimingCollector()
+    with timings.span("scan", unit="files"):
+        md_files = list(
+            iter_markdown_files(
+                folder,
+                include_hidden=include_hidden,
+                include=include,
+                fs=fs,
+                logger=logger,
             )
-            yield prompt_data
+        )
+
+    errors: Counter[str] = Counter()
+    for md_file in md_files:
+        try:
+            with timings.span("scan", unit="files") as span:
+                content = fs.read_text(md_file)
+                span.add(nbytes=len(content.encode()))
+            with timings.span("parse", unit="files") as span:
+                prompt_data = _parse_markdown_file(
+                    md_file,
+                    folder,
+                    content,
+                    skip_frontmatter,
+                    namespace_separator=namespace_separator,
+                    strict_frontmatter=strict_frontmatter,
+                    logger=logger,
+                )
+                span.add()
+        except OSError as e:
+            errors[classify_read_error(e)] += 1
+            logger.error(f"failed to read {md_file}: {describe_read_error(e)}")
+            if failures is not None:
+                failures.append((md_file, describe_read_error(e)))
         except Exception as e:
-            logger.warning(f"failed to process {md_file}: {e}")
-            continue
+            logger.error(f"failed to process {md_file}: {e}")
+            if failures is not None:
+                failures.append((md_file, str(e)))
+        else:
+            logger.debug(f"loaded prompt '{prompt_data.name}' from {md_file}")
+            yield prompt_data
+
+    if errors:
+        counts = ", ".join(f"{count} {kind}" for kind, count in errors.items())
+        logger.error(f"{errors.total()} file(s) could not be read: {counts}")
+    if read_errors is not None:
+        read_errors.update(errors)
+
+
+def scan_markdown_folders(
+    folders: Iterable[Path],
+    skip_frontmatter: bool,
+    *,
+    include_hidden: bool = False,
+    namespace_separator: Optional[str] = None,
+    strict_frontmatter: bool = False,
+    include: Optional[list[str]] = None,
+    read_errors: Optional[Counter[str]] = None,
+    duplicates: Optional[list[str]] = None,
+    failures: Optional[list[tuple[Path, str]]] = None,
+    timings: Optional[TimingCollector] = None,
+    fs: FileSystemInterface = DefaultFileSystem(),
+    logger: LoggerInterface = DefaultLogger(),
+) -> list[tuple[Path, PromptData]]:
+    """
+    Scan several folders, later folders overriding prompts with the same name.
+
+    Within one folder, the lexicographically first path wins when several
+    files share a prompt name.
+
+    Args:
+        folders: Folders to scan, in increasing order of precedence
+        skip_frontmatter: Whether to skip frontmatter processing
+        include_hidden: Whether to load hidden files and directories
+        namespace_separator: Joins directories into default prompt names, if set
+        strict_frontmatter: Whether files with invalid leading JSON
+            frontmatter fail to load instead of using it as prompt content
+        include: Gitignore-style patterns files must match, if given
+        read_errors: Counter updated with the class of each file read failure
+        duplicates: List extended with each name shared by files in one folder
+        failures: List extended with each file that failed to load and why
+        timings: Collector recording the walk and file reads as the scan
+            phase and frontmatter parsing as the parse phase
+        fs: File system interface for file operations
+        logger: Logger interface for warning messages
+
+    Returns:
+        Each prompt with the folder it was loaded from
+    """
+    found: dict[str, tuple[Path, PromptData]] = {}
+    for folder in folders:
+        in_folder: dict[str, PromptData] = {}
+        for prompt_data in scan_markdown_files(
+            folder,
+            skip_frontmatter,
+            include_hidden=include_hidden,
+            namespace_separator=namespace_separator,
+            strict_frontmatter=strict_frontmatter,
+            include=include,
+            read_errors=read_errors,
+            failures=failures,
+            timings=timings,
+            fs=fs,
+            logger=logger,
+        ):
+            other = in_folder.get(prompt_data.name)
+            if other is None:
+                in_folder[prompt_data.name] = prompt_data
+                continue
+            # Walk order varies by file system, pick a winner by path instead
+            kept, dropped = sorted([other, prompt_data], key=lambda p: str(p.path))
+            logger.warning(
+                f"duplicate prompt name '{prompt_data.name}' in {kept.path} "
+                f"and {dropped.path}, using {kept.path}"
+            )
+            if duplicates is not None:
+                duplicates.append(prompt_data.name)
+            in_folder[prompt_data.name] = kept
+
+        for prompt_data in in_folder.values():
+            previous = found.get(prompt_data.name)
+            if previous:
+                logger.warning(
+                    f"prompt '{prompt_data.name}' from {prompt_data.path} "
+                    f"overrides {previous[1].path}"
+                )
+            found[prompt_data.name] = (folder, prompt_data)
+    return list(found.values())
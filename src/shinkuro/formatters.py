@@ -2,16 +2,100 @@
 
 import re
 import string
-from typing import Protocol, Dict, Any
-from .model import FormatterType
+from typing import Protocol, Dict, Any, List, Optional, Tuple
+from .errors import InvalidArgumentType
+from .model import FormatterType, PromptData
 
 # Python identifier pattern for argument names and template variables
 IDENTIFIER_PATTERN = r"^[a-zA-Z_][a-zA-Z0-9_]*$"
+_IDENTIFIER_RE = re.compile(IDENTIFIER_PATTERN)
 
 
 def validate_variable_name(name: str) -> bool:
     """Validate that a variable name is a valid Python identifier."""
-    return bool(re.match(IDENTIFIER_PATTERN, name))
+    return bool(_IDENTIFIER_RE.match(name))
+
+
+# Backslash escapes, rewritten to the doubled forms the formatters already treat
+# as literal text
+_BRACE_ESCAPE = re.compile(r"\\([{}])")
+_DOLLAR_ESCAPE = re.compile(r"\\\$")
+
+# Escaped braces are matched first so {{#if x}} stays literal text
+_CONDITIONAL_TOKEN = re.compile(r"\{\{|\}\}|\{#if\s+([^{}]*?)\s*\}|\{/if\}")
+
+
+def _apply_format_spec(name: str, value: Any, spec: str) -> str:
+    """
+    Format a value, converting text to a number for numeric specs.
+
+    Raises:
+        InvalidArgumentType: If the spec needs a number and the value is not one
+    """
+    try:
+        return format(value, spec)
+    except ValueError:
+        # Arguments arrive as text, so {amount!s:.2f} needs a number
+        for convert in (int, float):
+            try:
+                return format(convert(value), spec)
+            except (TypeError, ValueError):
+                continue
+        raise InvalidArgumentType(name, "number", str(value))
+
+
+def split_conditionals(content: str) -> List[Tuple[Optional[str], str]]:
+    """
+    Split brace template content into plain and conditional segments.
+
+    Args:
+        content: Template content with optional {#if name}...{/if} blocks
+
+    Returns:
+        List of (condition, text) pairs, condition is None for plain text
+
+    Raises:
+        ValueError: If blocks are nested, unterminated or unmatched
+    """
+    segments: List[Tuple[Optional[str], str]] = []
+    condition: Optional[str] = None
+    start = 0
+    for match in _CONDITIONAL_TOKEN.finditer(content):
+        if match.group(0) in ("{{", "}}"):
+            continue
+        name = match.group(1)
+        if name is not None:
+            if condition is not None:
+                raise ValueError(
+                    f"Invalid template syntax: nested {match.group(0)} "
+                    f"inside {{#if {condition}}}"
+                )
+            if not validate_variable_name(name):
+                raise ValueError(f"Invalid variable name: {name}")
+            segments.append((None, content[start : match.start()]))
+            condition = name
+        else:
+            if condition is None:
+                raise ValueError(
+                    "Invalid template syntax: {/if} without a matching {#if}"
+                )
+            segments.append((condition, content[start : match.start()]))
+            condition = None
+        start = match.end()
+    if condition is not None:
+        raise ValueError(f"Invalid template syntax: unterminated {{#if {condition}}}")
+    segments.append((None, content[start:]))
+    return segments
+
+
+def _double_braces(content: str) -> str:
+    """Rewrite \\{ and \\} escapes as {{ and }}."""
+    return _BRACE_ESCAPE.sub(r"\1\1", content)
+
+
+def _double_dollars(content: str) -> str:
+    """Rewrite \\$ escapes as $$."""
+    return _DOLLAR_ESCAPE.sub("$$", content)
 
 
 class FormatterInterface(Protocol):
@@ -21,15 +105,65 @@ class FormatterInterface(Protocol):
         """Extract and validate argument names from content."""
         ...
 
+    def extract_defaults(self, content: str) -> Dict[str, str]:
+        """Extract default values written inline in the template."""
+        ...
+
     def format(self, content: str, variables: Dict[str, Any]) -> str:
         """Format content with variables."""
         ...
 
 
 class BraceFormatter:
-    """Formatter for {var} syntax."""
+    """
+    Formatter for {var} syntax with optional {var:default} inline defaults.
+
+    With a conversion the text after the colon is a Python format spec
+    instead, e.g. {amount!s:>10} pads the value. Sections wrapped in
+    {#if var}...{/if} are only rendered when var is provided and non-empty.
+    Literal braces are written as {{ and }} or as \\{ and \\}.
+    """
 
     def extract_arguments(self, content: str) -> set[str]:
+        arguments = set()
+        for condition, text in split_conditionals(_double_braces(content)):
+            if condition:
+                arguments.add(condition)
+            arguments |= self._extract_fields(text)
+        return arguments
+
+    def extract_defaults(self, content: str) -> Dict[str, str]:
+        formatter = string.Formatter()
+        defaults: Dict[str, str] = {}
+        unconditional: set[str] = set()
+        conditional: set[str] = set()
+        for condition, text in split_conditionals(_double_braces(content)):
+            if condition:
+                conditional.add(condition)
+            for _, field_name, default, conversion in formatter.parse(text):
+                if field_name:
+                    (conditional if condition else unconditional).add(field_name)
+                    if not default or conversion:
+                        continue
+                    existing = defaults.setdefault(field_name, default)
+                    if existing != default:
+                        raise ValueError(
+                            f"Conflicting inline defaults for '{field_name}': "
+                            f"'{existing}' and '{default}'"
+                        )
+        # Variables only used inside conditional sections are optional
+        for name in conditional - unconditional:
+            defaults.setdefault(name, "")
+        return defaults
+
+    def format(self, content: str, variables: Dict[str, Any]) -> str:
+        return "".join(
+            self._format_fields(text, variables)
+            for condition, text in split_conditionals(_double_braces(content))
+            if condition is None or variables.get(condition) not in (None, "")
+        )
+
+    def _extract_fields(self, content: str) -> set[str]:
         formatter = string.Formatter()
         arguments = set()
         for _, field_name, _, _ in formatter.parse(content):
@@ -39,14 +173,32 @@ class BraceFormatter:
                 arguments.add(field_name)
         return arguments
 
-    def format(self, content: str, variables: Dict[str, Any]) -> str:
-        return content.format(**variables)
+    def _format_fields(self, content: str, variables: Dict[str, Any]) -> str:
+        formatter = string.Formatter()
+        parts = []
+        for literal, field_name, spec, conversion in formatter.parse(content):
+            parts.append(literal)
+            if field_name is None:
+                continue
+            if field_name in variables:
+                value = variables[field_name]
+            elif spec and not conversion:
+                value = spec
+            else:
+                raise KeyError(field_name)
+            if conversion:
+                value = formatter.convert_field(value, conversion)
+                parts.append(_apply_format_spec(field_name, value, spec))
+            else:
+                parts.append(str(value))
+        return "".join(parts)
 
 
 class DollarFormatter:
-    """Formatter for $var syntax."""
+    """Formatter for $var syntax, literal $ is written as $$ or \\$."""
 
     def extract_arguments(self, content: str) -> set[str]:
+        content = _double_dollars(content)
         try:
             template = string.Template(content)
             arguments = set()
@@ -60,17 +212,58 @@ class DollarFormatter:
         except ValueError as e:
             raise ValueError(f"Invalid template syntax: {e}")
 
+    def extract_defaults(self, content: str) -> Dict[str, str]:
+        return {}
+
     def format(self, content: str, variables: Dict[str, Any]) -> str:
-        template = string.Template(content)
+        template = string.Template(_double_dollars(content))
         return template.safe_substitute(variables)
 
 
+class HandlebarsFormatter:
+    """Formatter for {{var}} syntax."""
+
+    # Matches {{name}} with optional whitespace inside the braces
+    PATTERN = re.compile(r"\{\{\s*([^{}]*?)\s*\}\}")
+
+    def extract_arguments(self, content: str) -> set[str]:
+        arguments = set()
+        for match in self.PATTERN.finditer(content):
+            param = match.group(1)
+            if not validate_variable_name(param):
+                raise ValueError(f"Invalid variable name: {param}")
+            arguments.add(param)
+        return arguments
+
+    def extract_defaults(self, content: str) -> Dict[str, str]:
+        return {}
+
+    def format(self, content: str, variables: Dict[str, Any]) -> str:
+        def replace(match: re.Match[str]) -> str:
+            param = match.group(1)
+            if param in variables:
+                return str(variables[param])
+            return match.group(0)
+
+        return self.PATTERN.sub(replace, content)
+
+
 def get_formatter(formatter_type: FormatterType) -> FormatterInterface:
     """Get formatter by type."""
     formatters = {
         FormatterType.BRACE: BraceFormatter(),
         FormatterType.DOLLAR: DollarFormatter(),
+        FormatterType.HANDLEBARS: HandlebarsFormatter(),
     }
     if formatter_type not in formatters:
         raise ValueError(f"Unknown formatter: {formatter_type}")
     return formatters[formatter_type]
+
+
+def resolve_formatter(
+    prompt_data: PromptData, default: FormatterInterface
+) -> FormatterInterface:
+    """Get the formatter of a prompt, falling back to the global one."""
+    if prompt_data.variable_format is None:
+        return default
+    return get_formatter(prompt_data.variable_format)
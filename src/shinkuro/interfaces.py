@@ -1,9 +1,32 @@
 """Interfaces for dependency injection and testing."""
 
+import atexit
+import base64
+import json
+import os
+import shlex
+import shutil
 import sys
+import tempfile
+from contextlib import nullcontext, suppress
 from pathlib import Path
-from typing import Iterator, Protocol
-from git import Repo
+from typing import ClassVar, ContextManager, Dict, Iterator, List, Optional, Protocol
+from git import GitCommandError, Repo
+from .model import LogFormat, LogLevel
+
+# Fragments of git error output caused by missing or rejected credentials
+_AUTH_ERROR_MARKERS = (
+    "Authentication failed",
+    "could not read Username",
+    "terminal prompts disabled",
+    "Permission denied (publickey",
+)
+
+# Username sent with a token when none is configured, accepted by GitHub
+_DEFAULT_TOKEN_USERNAME = "x-access-token"
+
+# Askpass program answering the ssh key passphrase prompt from the environment
+_ASKPASS_SCRIPT = '#!/bin/sh\nprintf "%s\\n" "$SHINKURO_SSH_KEY_PASSPHRASE"\n'
 
 
 class FileSystemInterface(Protocol):
@@ -25,26 +48,54 @@ class FileSystemInterface(Protocol):
         """Check if path is a directory."""
         ...
 
+    def mtime(self, path: Path) -> int:
+        """Get the modification time of a file in nanoseconds."""
+        ...
+
 
 class LoggerInterface(Protocol):
     """Protocol for logging operations."""
 
+    def error(self, message: str) -> None:
+        """Log a failure, e.g. a file that could not be read."""
+        ...
+
     def warning(self, message: str) -> None:
         """Log a warning message."""
         ...
 
+    def info(self, message: str) -> None:
+        """Log a progress message."""
+        ...
+
+    def debug(self, message: str) -> None:
+        """Log a diagnostic message."""
+        ...
+
 
 class GitInterface(Protocol):
     """Protocol for git operations."""
 
-    def clone(self, url: str, path: Path) -> None:
-        """Clone a git repository."""
+    def clone(self, url: str, path: Path, ref: Optional[str] = None) -> None:
+        """Clone a git repository, checking out a branch, tag or commit if given."""
         ...
 
     def pull(self, path: Path) -> None:
         """Pull latest changes from remote."""
         ...
 
+    def on_branch(self, path: Path) -> bool:
+        """Check if a branch is checked out, as opposed to a tag or commit."""
+        ...
+
+    def lfs_available(self) -> bool:
+        """Check if Git LFS is installed."""
+        ...
+
+    def lfs_pull(self, path: Path) -> None:
+        """Fetch and check out Git LFS objects."""
+        ...
+
 
 class DefaultFileSystem:
     """Default file system implementation using pathlib."""
@@ -61,21 +112,175 @@ class DefaultFileSystem:
     def is_dir(self, path: Path) -> bool:
         return path.is_dir()
 
+    def mtime(self, path: Path) -> int:
+        return path.stat().st_mtime_ns
+
 
 class DefaultLogger:
-    """Default logger implementation using stderr."""
+    """
+    Default logger implementation using stderr.
+
+    Messages never go to stdout, which carries the stdio transport.
+    """
+
+    # Shared by all instances since most are created as default arguments
+    level: ClassVar[LogLevel] = LogLevel.WARN
+    format: ClassVar[LogFormat] = LogFormat.TEXT
+
+    def error(self, message: str) -> None:
+        self._write(LogLevel.ERROR, "Error", message)
 
     def warning(self, message: str) -> None:
-        print(f"Warning: {message}", file=sys.stderr)
+        self._write(LogLevel.WARN, "Warning", message)
+
+    def info(self, message: str) -> None:
+        self._write(LogLevel.INFO, "Info", message)
+
+    def debug(self, message: str) -> None:
+        self._write(LogLevel.DEBUG, "Debug", message)
+
+    def _write(self, level: LogLevel, label: str, message: str) -> None:
+        levels = list(LogLevel)
+        if levels.index(level) > levels.index(DefaultLogger.level):
+            return
+        if DefaultLogger.format == LogFormat.JSON:
+            line = json.dumps({"level": level.value, "message": message})
+        else:
+            line = f"{label}: {message}"
+        print(line, file=sys.stderr)
 
 
 class DefaultGit:
-    """Default git implementation using GitPython."""
+    """
+    Default git implementation using GitPython.
 
-    def clone(self, url: str, path: Path) -> None:
+    A token is sent as an HTTP authorization header and an SSH key is passed
+    to ssh, both through the environment of each git command so neither is
+    written to the repository config. Without them git falls back to the
+    ssh-agent and configured credential helpers.
+    """
+
+    def __init__(
+        self,
+        token: Optional[str] = None,
+        ssh_key: Optional[str] = None,
+        *,
+        username: Optional[str] = None,
+        ssh_key_passphrase: Optional[str] = None,
+    ):
+        self.token = token
+        self.ssh_key = ssh_key
+        self.username = username
+        self.ssh_key_passphrase = ssh_key_passphrase
+        self._askpass: Optional[str] = None
+
+    def clone(self, url: str, path: Path, ref: Optional[str] = None) -> None:
         path.parent.mkdir(parents=True, exist_ok=True)
-        Repo.clone_from(url, path, depth=1)
+        env = self._env()
+        options = {"env": env} if env else {}
+        try:
+            if not ref:
+                Repo.clone_from(url, path, depth=1, **options)
+                return
+            try:
+                # Branches and tags can be cloned shallowly
+                Repo.clone_from(url, path, depth=1, branch=ref, **options)
+            except GitCommandError:
+                # Commits need the full history to be checked out
+                shutil.rmtree(path, ignore_errors=True)
+                repo = Repo.clone_from(url, path, **options)
+                repo.git.checkout(ref)
+        except GitCommandError as e:
+            self._raise_if_auth_error(e, url)
+            raise
 
     def pull(self, path: Path) -> None:
         repo = Repo(path)
-        repo.remotes.origin.pull()
+        try:
+            with self._credentials(repo):
+                repo.remotes.origin.pull()
+        except GitCommandError as e:
+            self._raise_if_auth_error(e, repo.remotes.origin.url)
+            raise
+
+    def on_branch(self, path: Path) -> bool:
+        return not Repo(path).head.is_detached
+
+    def lfs_available(self) -> bool:
+        return shutil.which("git-lfs") is not None
+
+    def lfs_pull(self, path: Path) -> None:
+        repo = Repo(path)
+        with self._credentials(repo):
+            repo.git.lfs("pull")
+
+    def _env(self) -> Dict[str, str]:
+        """Build environment variables carrying the configured credentials."""
+        env: Dict[str, str] = {}
+        if self.token:
+            user = self.username or _DEFAULT_TOKEN_USERNAME
+            basic = base64.b64encode(f"{user}:{self.token}".encode()).decode()
+            env["GIT_CONFIG_COUNT"] = "1"
+            env["GIT_CONFIG_KEY_0"] = "http.extraHeader"
+            env["GIT_CONFIG_VALUE_0"] = f"Authorization: Basic {basic}"
+        if self.ssh_key:
+            key = shlex.quote(self.ssh_key)
+            env["GIT_SSH_COMMAND"] = f"ssh -i {key} -o IdentitiesOnly=yes"
+        if self.ssh_key_passphrase:
+            # Requires OpenSSH 8.4 or later to use askpass without a display
+            env["SSH_ASKPASS"] = self._askpass_program()
+            env["SSH_ASKPASS_REQUIRE"] = "force"
+            env["SHINKURO_SSH_KEY_PASSPHRASE"] = self.ssh_key_passphrase
+        if env:
+            # Fail instead of waiting for a password on the MCP stdio stream
+            env["GIT_TERMINAL_PROMPT"] = "0"
+        return env
+
+    def _askpass_program(self) -> str:
+        """Write the askpass script once, removing it when the process exits."""
+        if self._askpass is None:
+            fd, path = tempfile.mkstemp(prefix="shinkuro-askpass-", suffix=".sh")
+            with os.fdopen(fd, "w") as f:
+                f.write(_ASKPASS_SCRIPT)
+            os.chmod(path, 0o700)
+            atexit.register(_remove_file, path)
+            self._askpass = path
+        return self._askpass
+
+    def _credentials(self, repo: Repo) -> ContextManager[object]:
+        """Apply the configured credentials to git commands run on repo."""
+        env = self._env()
+        return repo.git.custom_environment(**env) if env else nullcontext()
+
+    def _raise_if_auth_error(self, error: GitCommandError, url: str) -> None:
+        """Replace a git error caused by failed authentication with a readable one."""
+        stderr = str(error.stderr or "")
+        if not any(marker in stderr for marker in _AUTH_ERROR_MARKERS):
+            return
+
+        tried: List[str] = []
+        if url.startswith(("http://", "https://")):
+            if self.token:
+                user = self.username or _DEFAULT_TOKEN_USERNAME
+                tried.append(f"token for user '{user}'")
+            tried.append("git credential helpers")
+            hint = "set --git-token (GIT_TOKEN) and --git-username (GIT_USERNAME)"
+        else:
+            if self.ssh_key:
+                passphrase = " with passphrase" if self.ssh_key_passphrase else ""
+                tried.append(f"SSH key {self.ssh_key}{passphrase}")
+            else:
+                tried.extend(["ssh-agent", "default SSH keys"])
+            hint = (
+                "set --git-ssh-key (GIT_SSH_KEY) and "
+                "--git-ssh-key-passphrase (GIT_SSH_KEY_PASSPHRASE)"
+            )
+        raise ValueError(
+            f"Git authentication failed, tried {', '.join(tried)}; {hint}"
+        ) from error
+
+
+def _remove_file(path: str) -> None:
+    """Delete a file, ignoring errors if it is already gone."""
+    with suppress(OSError):
+        os.remove(path)
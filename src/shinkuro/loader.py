@@ -1,8 +1,45 @@
 """Prompt source loading and resolution."""
 
+import re
+from dataclasses import replace
 from pathlib import Path
-from .remote.git import get_local_cache_path, clone_or_update_repo
-from typing import Optional
+from .model import ROLES, PromptData
+from .remote.git import get_local_cache_path, clone_or_update_repo, fetch_lfs_objects
+from .interfaces import DefaultGit, GitInterface
+from typing import Any, Dict, Iterable, List, Optional
+
+# Prompt fields that can be overridden for all prompts with --override
+OVERRIDE_KEYS = ("role", "title", "description", "description_prefix")
+
+
+def _compile_patterns(patterns: Iterable[str], kind: str) -> list[re.Pattern[str]]:
+    compiled = []
+    for pattern in patterns:
+        try:
+            compiled.append(re.compile(pattern))
+        except re.error as e:
+            raise ValueError(f"Invalid {kind} pattern '{pattern}': {e}")
+    return compiled
+
+
+class ContentFilter:
+    """
+    Select prompts to serve by regex matches against their content.
+
+    When allow patterns are given, a prompt must match at least one of them.
+    A prompt matching any deny pattern is never served.
+    """
+
+    def __init__(self, allow: Iterable[str] = (), deny: Iterable[str] = ()):
+        self.allow = _compile_patterns(allow, "content allow")
+        self.deny = _compile_patterns(deny, "content deny")
+
+    def matches(self, prompt_data: PromptData) -> bool:
+        """Check if the prompt passes the allow and deny patterns."""
+        content = prompt_data.content
+        if self.allow and not any(p.search(content) for p in self.allow):
+            return False
+        return not any(p.search(content) for p in self.deny)
 
 
 def get_folder_path(
@@ -10,6 +47,9 @@ def get_folder_path(
     git_url: Optional[str],
     cache_dir: Path,
     auto_pull: bool,
+    git_lfs: bool = False,
+    git_ref: Optional[str] = None,
+    git: GitInterface = DefaultGit(),
 ) -> Path:
     """
     Determine the folder path to scan for prompts.
@@ -19,6 +59,9 @@ def get_folder_path(
         git_url: Git repository URL
         cache_dir: Directory to cache remote repositories
         auto_pull: Whether to refresh local cache on startup
+        git_lfs: Whether to fetch Git LFS objects after clone/pull
+        git_ref: Branch, tag or commit of the git repository to check out
+        git: Git interface for git operations, carrying any credentials
 
     Returns:
         Path to folder containing markdown files
@@ -27,8 +70,10 @@ def get_folder_path(
         ValueError: If neither folder nor git_url is provided
     """
     if git_url:
-        repo_path = get_local_cache_path(git_url, cache_dir)
-        clone_or_update_repo(git_url, repo_path, auto_pull)
+        repo_path = get_local_cache_path(git_url, cache_dir, git_ref)
+        clone_or_update_repo(git_url, repo_path, auto_pull, ref=git_ref, git=git)
+        if git_lfs:
+            fetch_lfs_objects(repo_path, git=git)
 
         if folder:
             # Use folder as subfolder within the repo
@@ -39,3 +84,117 @@ def get_folder_path(
         if not folder:
             raise ValueError("Either folder or git-url must be provided")
         return Path(folder)
+
+
+def parse_overrides(values: Iterable[str]) -> Dict[str, str]:
+    """
+    Parse key=value overrides applied to every prompt.
+
+    Args:
+        values: Overrides like role=assistant or description_prefix=[beta]
+
+    Returns:
+        Override values keyed by field, later values winning
+
+    Raises:
+        ValueError: If an override is malformed, has an unknown key or an
+            invalid value
+    """
+    overrides = {}
+    for value in values:
+        key, sep, field_value = value.partition("=")
+        key = key.strip()
+        if not sep:
+            raise ValueError(f"Invalid override '{value}', expected key=value")
+        if key not in OVERRIDE_KEYS:
+            raise ValueError(
+                f"Unknown override key '{key}', expected one of: "
+                f"{', '.join(OVERRIDE_KEYS)}"
+            )
+        if key == "role" and field_value not in ROLES:
+            raise ValueError(
+                f"Invalid role '{field_value}', expected one of: {', '.join(ROLES)}"
+            )
+        overrides[key] = field_value
+    return overrides
+
+
+def apply_overrides(prompt_data: PromptData, overrides: Dict[str, str]) -> PromptData:
+    """
+    Apply parsed overrides to a prompt.
+
+    A description override replaces localized descriptions as well, and a
+    description prefix is prepended to every description.
+
+    Args:
+        prompt_data: Prompt loaded from its source
+        overrides: Overrides returned by parse_overrides
+
+    Returns:
+        A copy of the prompt with the overrides applied
+    """
+    if not overrides:
+        return prompt_data
+
+    changes: Dict[str, Any] = {}
+    description = prompt_data.description
+    descriptions = prompt_data.descriptions
+    if "description" in overrides:
+        description = overrides["description"]
+        descriptions = {}
+    if "description_prefix" in overrides:
+        prefix = overrides["description_prefix"]
+        description = prefix + description
+        descriptions = {key: prefix + value for key, value in descriptions.items()}
+    changes["description"] = description
+    changes["descriptions"] = descriptions
+
+    if "title" in overrides:
+        changes["title"] = overrides["title"]
+    if "role" in overrides:
+        changes["role"] = overrides["role"]
+    return replace(prompt_data, **changes)
+
+
+def get_folder_paths(
+    folders: List[str],
+    git_url: Optional[str],
+    cache_dir: Path,
+    auto_pull: bool,
+    git_lfs: bool = False,
+    git_ref: Optional[str] = None,
+    git: GitInterface = DefaultGit(),
+) -> List[Path]:
+    """
+    Determine the folder paths to scan for prompts.
+
+    The repository is cloned or updated once, and each folder is resolved
+    within it when git_url is given.
+
+    Args:
+        folders: Paths to local folders or subfolders within git repo
+        git_url: Git repository URL
+        cache_dir: Directory to cache remote repositories
+        auto_pull: Whether to refresh local cache on startup
+        git_lfs: Whether to fetch Git LFS objects after clone/pull
+        git_ref: Branch, tag or commit of the git repository to check out
+        git: Git interface for git operations, carrying any credentials
+
+    Returns:
+        Paths to folders containing markdown files, in the given order
+
+    Raises:
+        ValueError: If neither folders nor git_url is provided
+    """
+    if not folders:
+        return [
+            get_folder_path(
+                None, git_url, cache_dir, auto_pull, git_lfs, git_ref, git=git
+            )
+        ]
+    if git_url:
+        repo_path = get_folder_path(
+            None, git_url, cache_dir, auto_pull, git_lfs, git_ref, git=git
+        )
+        return [repo_path / folder for folder in folders]
+    return [Path(folder) for folder in folders]
@@ -1,17 +1,45 @@
 """Main entry point for shinkuro MCP server."""
 
+import json
 import typer
+from collections import Counter
+from functools import partial
 from pathlib import Path
 from fastmcp import FastMCP
 from typing_extensions import Annotated
 
 from . import __version__
-from .file.scan import scan_markdown_files
-from .loader import get_folder_path
+from .completions import register_completions
+from .config import config_callback
+from .doctor import Check, check_prompts, format_report, run_checks
+from .envvars import ENV_VAR_FOLDERS, ENV_VAR_LINES
+from .errors import PromptError, PromptErrorMiddleware
+from .file.read_errors import PERMISSION_DENIED
+from .file.scan import scan_markdown_folders, parse_markdown_file
+from .loader import (
+    ContentFilter,
+    apply_overrides,
+    get_folder_paths,
+    parse_overrides,
+)
 from .prompts.markdown import MarkdownPrompt
-from .formatters import get_formatter
-from .model import FormatterType
-from typing import Optional
+from .formatters import get_formatter, resolve_formatter
+from .interfaces import DefaultFileSystem, DefaultGit, DefaultLogger
+from .model import (
+    FormatterType,
+    ListFormat,
+    LogFormat,
+    LogLevel,
+    PromptData,
+    Transport,
+)
+from .stats import collect_argument_usage
+from .redact import Redactor, warn_secrets
+from .render import complete_prompt_names, parse_render_args, render_prompt
+from .request_log import RequestLogMiddleware
+from .resources import register_file_resources, register_prompt_resources
+from .timing import TimingCollector
+from typing import Dict, List, Optional, Tuple
 
 
 def version_callback(value: bool):
@@ -21,11 +49,22 @@ def version_callback(value: bool):
 
 
 def app(
-    folder: Annotated[
+    _config: Annotated[
         Optional[str],
+        typer.Option(
+            "--config",
+            envvar="SHINKURO_CONFIG",
+            callback=config_callback,
+            is_eager=True,
+            help="TOML file with option defaults, overridden by flags and env vars (~/.shinkuro/config.toml if present)",
+        ),
+    ] = None,
+    folder: Annotated[
+        Optional[List[str]],
         typer.Option(
             envvar="FOLDER",
-            help="Path to local folder containing markdown files, or subfolder within git repo",
+            click_type=ENV_VAR_FOLDERS,
+            help="Path to local folder containing markdown files, or subfolder within git repo (repeatable, later folders override earlier ones)",
         ),
     ] = None,
     git_url: Annotated[
@@ -47,6 +86,51 @@ def app(
             help="Whether to refresh local cache on startup",
         ),
     ] = False,
+    git_ref: Annotated[
+        Optional[str],
+        typer.Option(
+            "--git-ref",
+            "--branch",
+            envvar="GIT_REF",
+            help="Branch, tag or commit of the git repository to use (default branch if not set)",
+        ),
+    ] = None,
+    git_token: Annotated[
+        Optional[str],
+        typer.Option(
+            envvar="GIT_TOKEN",
+            help="Access token for HTTPS git repositories, sent as an authorization header",
+        ),
+    ] = None,
+    git_username: Annotated[
+        Optional[str],
+        typer.Option(
+            envvar="GIT_USERNAME",
+            help="Username sent with --git-token (default x-access-token)",
+        ),
+    ] = None,
+    git_ssh_key: Annotated[
+        Optional[str],
+        typer.Option(
+            envvar="GIT_SSH_KEY",
+            help="Private key file for SSH git repositories, instead of the ssh-agent",
+        ),
+    ] = None,
+    git_ssh_key_passphrase: Annotated[
+        Optional[str],
+        typer.Option(
+            envvar="GIT_SSH_KEY_PASSPHRASE",
+            help="Passphrase of the --git-ssh-key file, requires OpenSSH 8.4+",
+        ),
+    ] = None,
+    git_lfs: Annotated[
+        bool,
+        typer.Option(
+            "--git-lfs",
+            envvar="GIT_LFS",
+            help="Fetch Git LFS objects after clone/pull",
+        ),
+    ] = False,
     variable_format: Annotated[
         FormatterType,
         typer.Option(envvar="VARIABLE_FORMAT", help="Template variable format"),
@@ -67,6 +151,225 @@ def app(
             help="Skip frontmatter processing and use raw markdown content",
         ),
     ] = False,
+    strict_frontmatter: Annotated[
+        bool,
+        typer.Option(
+            "--strict-frontmatter",
+            envvar="STRICT_FRONTMATTER",
+            help="Skip files whose leading JSON frontmatter is invalid instead of serving them as plain content",
+        ),
+    ] = False,
+    include_hidden: Annotated[
+        bool,
+        typer.Option(
+            "--include-hidden",
+            envvar="INCLUDE_HIDDEN",
+            help="Load markdown files from hidden files and directories",
+        ),
+    ] = False,
+    include: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            envvar="INCLUDE",
+            click_type=ENV_VAR_LINES,
+            help="Only load files matching this gitignore-style glob, e.g. prompts/**/*.md (repeatable)",
+        ),
+    ] = None,
+    namespace_by_dir: Annotated[
+        bool,
+        typer.Option(
+            "--namespace-by-dir",
+            envvar="NAMESPACE_BY_DIR",
+            help="Prefix default prompt names with their directory relative to the folder",
+        ),
+    ] = False,
+    namespace_separator: Annotated[
+        str,
+        typer.Option(
+            envvar="NAMESPACE_SEPARATOR",
+            help="Separator between directories and file name with --namespace-by-dir",
+        ),
+    ] = "/",
+    strict_names: Annotated[
+        bool,
+        typer.Option(
+            "--strict-names",
+            envvar="STRICT_NAMES",
+            help="Exit with an error if files in one folder share a prompt name",
+        ),
+    ] = False,
+    fail_on_permission_errors: Annotated[
+        bool,
+        typer.Option(
+            "--fail-on-permission-errors",
+            envvar="FAIL_ON_PERMISSION_ERRORS",
+            help="Exit with an error if any prompt file cannot be read due to permissions",
+        ),
+    ] = False,
+    fail_on_empty: Annotated[
+        bool,
+        typer.Option(
+            "--fail-on-empty",
+            envvar="FAIL_ON_EMPTY",
+            help="Exit with an error if no prompts are loaded",
+        ),
+    ] = False,
+    list_format: Annotated[
+        ListFormat,
+        typer.Option(
+            envvar="LIST_FORMAT",
+            help="Verbosity of prompts/list, compact omits arguments and shortens descriptions",
+        ),
+    ] = ListFormat.FULL,
+    default_locale: Annotated[
+        Optional[str],
+        typer.Option(
+            envvar="DEFAULT_LOCALE",
+            help="Locale used to select localized prompt descriptions, e.g. en or zh-CN",
+        ),
+    ] = None,
+    arg_value_max_total: Annotated[
+        int,
+        typer.Option(
+            envvar="ARG_VALUE_MAX_TOTAL",
+            help="Maximum combined byte size of argument values in a single request",
+        ),
+    ] = 1024 * 1024,
+    allow_extra_args: Annotated[
+        bool,
+        typer.Option(
+            "--allow-extra-args",
+            envvar="ALLOW_EXTRA_ARGS",
+            help="Ignore arguments a prompt does not declare instead of rejecting the request",
+        ),
+    ] = False,
+    redact: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            envvar="REDACT",
+            click_type=ENV_VAR_LINES,
+            help="Regex whose matches are replaced with *** in rendered prompts (repeatable)",
+        ),
+    ] = None,
+    content_allow: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            envvar="CONTENT_ALLOW",
+            click_type=ENV_VAR_LINES,
+            help="Only serve prompts whose content matches this regex (repeatable)",
+        ),
+    ] = None,
+    content_deny: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            envvar="CONTENT_DENY",
+            click_type=ENV_VAR_LINES,
+            help="Do not serve prompts whose content matches this regex (repeatable)",
+        ),
+    ] = None,
+    override: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            envvar="OVERRIDE",
+            click_type=ENV_VAR_LINES,
+            help="Override a field of every prompt as key=value, keys: role, title, description, description_prefix (repeatable)",
+        ),
+    ] = None,
+    redact_secrets: Annotated[
+        bool,
+        typer.Option(
+            "--redact-secrets",
+            envvar="REDACT_SECRETS",
+            help="Redact built-in secret patterns (AWS keys, GitHub/GitLab/Slack tokens, private keys)",
+        ),
+    ] = False,
+    lazy_reload: Annotated[
+        bool,
+        typer.Option(
+            "--lazy-reload",
+            envvar="LAZY_RELOAD",
+            help="Re-read a prompt file on get when it changed since it was loaded",
+        ),
+    ] = False,
+    expose_resources: Annotated[
+        bool,
+        typer.Option(
+            "--expose-resources",
+            envvar="EXPOSE_RESOURCES",
+            help="List each prompt file as a file:// resource returning its raw markdown",
+        ),
+    ] = False,
+    transport: Annotated[
+        Transport,
+        typer.Option(
+            envvar="TRANSPORT",
+            help="Serve over stdio or Streamable HTTP",
+        ),
+    ] = Transport.STDIO,
+    host: Annotated[
+        str,
+        typer.Option(envvar="HOST", help="Address to bind with --transport http"),
+    ] = "127.0.0.1",
+    port: Annotated[
+        int,
+        typer.Option(envvar="PORT", help="Port to listen on with --transport http"),
+    ] = 8000,
+    doctor: Annotated[
+        bool,
+        typer.Option(
+            "--doctor",
+            help="Diagnose the prompt source configuration and exit",
+        ),
+    ] = False,
+    check: Annotated[
+        bool,
+        typer.Option(
+            "--check",
+            help="Validate every prompt file without serving, exit with an error if any is invalid",
+        ),
+    ] = False,
+    emit_argument_usage_stats: Annotated[
+        bool,
+        typer.Option(
+            "--emit-argument-usage-stats",
+            help="Print declared vs. referenced argument usage as JSON and exit",
+        ),
+    ] = False,
+    render: Annotated[
+        Optional[str],
+        typer.Option(
+            help="Render the named prompt to stdout and exit, for testing prompt files",
+            autocompletion=complete_prompt_names,
+        ),
+    ] = None,
+    arg: Annotated[
+        Optional[List[str]],
+        typer.Option(
+            help="Argument for --render as key=value (repeatable)",
+        ),
+    ] = None,
+    print_timings: Annotated[
+        bool,
+        typer.Option(
+            "--print-timings",
+            envvar="PRINT_TIMINGS",
+            help="Print a startup timing breakdown to stderr",
+        ),
+    ] = False,
+    log_level: Annotated[
+        LogLevel,
+        typer.Option(
+            envvar="LOG_LEVEL",
+            help="Most verbose kind of message written to stderr",
+        ),
+    ] = LogLevel.WARN,
+    log_format: Annotated[
+        LogFormat,
+        typer.Option(
+            envvar="LOG_FORMAT",
+            help="Layout of messages on stderr, json writes one object per line",
+        ),
+    ] = LogFormat.TEXT,
     _version: Annotated[
         Optional[bool],
         typer.Option(
@@ -75,28 +378,195 @@ def app(
     ] = None,
 ):
     """Shinkuro - Universal prompt loader MCP server"""
+    DefaultLogger.level = log_level
+    DefaultLogger.format = log_format
     mcp = FastMCP(name="shinkuro")
+    folders = list(folder or [])
+    separator = namespace_separator if namespace_by_dir else None
 
-    try:
-        folder_path = get_folder_path(
-            folder, git_url, Path(cache_dir).expanduser(), auto_pull
+    if doctor:
+        checks: List[Check] = run_checks(
+            folders,
+            git_url,
+            Path(cache_dir).expanduser(),
+            skip_frontmatter,
+            get_formatter(variable_format),
+            auto_discover_args,
+            git_ref=git_ref,
+            include_hidden=include_hidden,
+            include=include,
+            namespace_separator=separator,
+            strict_frontmatter=strict_frontmatter,
         )
+        typer.echo(format_report(checks))
+        raise typer.Exit(0 if all(check.passed for check in checks) else 1)
+
+    timings = TimingCollector()
+
+    try:
+        with timings.span("fetch"):
+            folder_paths = get_folder_paths(
+                folders,
+                git_url,
+                Path(cache_dir).expanduser(),
+                auto_pull,
+                git_lfs,
+                git_ref,
+                git=DefaultGit(
+                    git_token,
+                    str(Path(git_ssh_key).expanduser()) if git_ssh_key else None,
+                    username=git_username,
+                    ssh_key_passphrase=git_ssh_key_passphrase,
+                ),
+            )
         formatter = get_formatter(variable_format)
-    except ValueError as e:
+        content_filter = ContentFilter(content_allow or [], content_deny or [])
+        overrides = parse_overrides(override or [])
+        render_args = parse_render_args(arg or [])
+        redactor = (
+            Redactor(redact or [], redact_secrets) if redact or redact_secrets else None
+        )
+    except (ValueError, TimeoutError) as e:
         typer.echo(f"Error: {e}", err=True)
         raise typer.Exit(1)
 
-    for prompt_data in scan_markdown_files(folder_path, skip_frontmatter):
-        prompt = MarkdownPrompt.from_prompt_data(
-            prompt_data, formatter, auto_discover_args
+    read_errors: Counter[str] = Counter()
+    duplicates: List[str] = []
+    failures: List[Tuple[Path, str]] = []
+    sources = scan_markdown_folders(
+        folder_paths,
+        skip_frontmatter,
+        include_hidden=include_hidden,
+        namespace_separator=separator,
+        strict_frontmatter=strict_frontmatter,
+        include=include,
+        read_errors=read_errors,
+        duplicates=duplicates,
+        failures=failures,
+        timings=timings,
+    )
+    sources = [
+        (folder_path, prompt_data)
+        for folder_path, prompt_data in sources
+        if content_filter.matches(prompt_data)
+    ]
+
+    if fail_on_permission_errors and read_errors[PERMISSION_DENIED]:
+        typer.echo(
+            f"Error: {read_errors[PERMISSION_DENIED]} prompt file(s) "
+            "could not be read due to permissions",
+            err=True,
+        )
+        raise typer.Exit(1)
+
+    if strict_names and duplicates:
+        typer.echo(
+            f"Error: duplicate prompt names: {', '.join(sorted(set(duplicates)))}",
+            err=True,
         )
-        mcp.add_prompt(prompt)
+        raise typer.Exit(1)
+
+    if emit_argument_usage_stats:
+        prompts = [prompt_data for _, prompt_data in sources]
+        typer.echo(json.dumps(collect_argument_usage(prompts, formatter), indent=2))
+        raise typer.Exit()
+
+    def build_prompt(prompt_data: PromptData) -> MarkdownPrompt:
+        return MarkdownPrompt.from_prompt_data(
+            prompt_data,
+            resolve_formatter(prompt_data, formatter),
+            auto_discover_args,
+            arg_value_max_total,
+            redactor,
+            default_locale,
+            list_format,
+            allow_extra_args,
+        )
+
+    if check:
+        checks = check_prompts(
+            (apply_overrides(prompt_data, overrides) for _, prompt_data in sources),
+            failures,
+            build_prompt,
+        )
+        typer.echo(format_report(checks))
+        raise typer.Exit(0 if all(result.passed for result in checks) else 1)
+
+    def reload_prompt(source: Path, folder_path: Path) -> MarkdownPrompt:
+        return build_prompt(
+            apply_overrides(
+                parse_markdown_file(
+                    source,
+                    folder_path,
+                    skip_frontmatter,
+                    namespace_separator=separator,
+                    strict_frontmatter=strict_frontmatter,
+                ),
+                overrides,
+            )
+        )
+
+    served: Dict[str, MarkdownPrompt] = {}
+    with timings.span("build", unit="prompts") as span:
+        for folder_path, prompt_data in sources:
+            prompt_data = apply_overrides(prompt_data, overrides)
+            if redactor:
+                warn_secrets(prompt_data, redactor)
+            prompt = build_prompt(prompt_data)
+            if lazy_reload and prompt_data.path:
+                source = prompt_data.path
+                prompt.enable_lazy_reload(
+                    partial(reload_prompt, source, folder_path),
+                    partial(DefaultFileSystem().mtime, source),
+                )
+            mcp.add_prompt(prompt)
+            served[prompt.name] = prompt
+            span.add()
+
+    if fail_on_empty and not served:
+        typer.echo(
+            "Error: no prompts were loaded, check --folder, --git-url and --git-ref",
+            err=True,
+        )
+        raise typer.Exit(1)
+
+    if render is not None:
+        if render not in served:
+            typer.echo(f"Error: unknown prompt '{render}'", err=True)
+            raise typer.Exit(1)
+        try:
+            typer.echo(render_prompt(served[render], render_args))
+        except PromptError as e:
+            typer.echo(f"Error: {e}", err=True)
+            raise typer.Exit(1)
+        raise typer.Exit()
+
+    register_prompt_resources(mcp, served, redactor)
+    if expose_resources:
+        register_file_resources(mcp, [data for _, data in sources], redactor)
+    register_completions(mcp, served)
+    mcp.add_middleware(PromptErrorMiddleware())
+    if log_level == LogLevel.DEBUG:
+        mcp.add_middleware(RequestLogMiddleware())
+
+    DefaultLogger().info(f"startup timings: {timings.summary()}")
+    if print_timings:
+        typer.echo(f"Startup timings: {timings.summary()}", err=True)
+
+    DefaultLogger().info(f"serving {len(served)} prompt(s) over {transport.value}")
+    if transport == Transport.HTTP:
+        mcp.run(transport="http", host=host, port=port)
+    else:
+        mcp.run()
+
 
-    mcp.run()
+# Like typer.run, plus --install-completion and --show-completion
+cli = typer.Typer(add_completion=True)
+cli.command()(app)
 
 
 def main():
-    typer.run(app)
+    cli()
 
 
 if __name__ == "__main__":
@@ -1,13 +1,64 @@
-from typing import List, Optional
-from dataclasses import dataclass
+from pathlib import Path
+from typing import Dict, List, Literal, Optional, get_args
+from dataclasses import dataclass, field
 from enum import Enum
 
 
+# Message roles allowed by MCP
+Role = Literal["user", "assistant"]
+ROLES: tuple[str, ...] = get_args(Role)
+
+
+def role_marker(role: str) -> str:
+    """Line in prompt content that starts a message with the given role."""
+    return f"<!-- role: {role} -->"
+
+
 class FormatterType(Enum):
     """Available template formatter types."""
 
     BRACE = "brace"
     DOLLAR = "dollar"
+    HANDLEBARS = "handlebars"
+
+
+class ArgumentType(Enum):
+    """Kind of value an argument accepts, substituted as text in all cases."""
+
+    STRING = "string"
+    NUMBER = "number"
+    BOOLEAN = "boolean"
+
+
+class ListFormat(Enum):
+    """Verbosity of prompts/list entries."""
+
+    FULL = "full"
+    COMPACT = "compact"
+
+
+class Transport(Enum):
+    """MCP transport the server listens on."""
+
+    STDIO = "stdio"
+    HTTP = "http"
+
+
+class LogLevel(Enum):
+    """Most verbose kind of message written to stderr, in increasing order."""
+
+    OFF = "off"
+    ERROR = "error"
+    WARN = "warn"
+    INFO = "info"
+    DEBUG = "debug"
+
+
+class LogFormat(Enum):
+    """Layout of messages written to stderr."""
+
+    TEXT = "text"
+    JSON = "json"
 
 
 @dataclass
@@ -18,11 +69,15 @@ class Argument:
         name: Parameter name for template substitution
         description: Human-readable description of the parameter
         default: Default value if parameter not provided
+        choices: Suggested values offered by argument completion
+        type: Kind of value accepted at render time
     """
 
     name: str
     description: str
     default: Optional[str] = None
+    choices: List[str] = field(default_factory=list)
+    type: ArgumentType = ArgumentType.STRING
 
 
 @dataclass
@@ -35,6 +90,11 @@ class PromptData:
         description: Brief description of prompt purpose
         arguments: Template arguments this prompt accepts
         content: Template content for variable substitution
+        path: Source file the prompt was loaded from, if any
+        post: Post-processors applied in order to the rendered text
+        descriptions: Localized descriptions keyed by locale
+        role: Role of the rendered message, user or assistant
+        variable_format: Template variable format overriding the global one
     """
 
     name: str
@@ -42,3 +102,21 @@ class PromptData:
     description: str
     arguments: List[Argument]
     content: str
+    path: Optional[Path] = None
+    post: List[str] = field(default_factory=list)
+    descriptions: Dict[str, str] = field(default_factory=dict)
+    role: Role = "user"
+    variable_format: Optional[FormatterType] = None
+
+    def localized_description(self, locale: Optional[str]) -> str:
+        """Select the description for a locale, falling back to its language."""
+        if not locale or not self.descriptions:
+            return self.description
+
+        normalized = {
+            key.lower().replace("_", "-"): value
+            for key, value in self.descriptions.items()
+        }
+        locale = locale.lower().replace("_", "-")
+        language = locale.split("-")[0]
+        return normalized.get(locale) or normalized.get(language) or self.description
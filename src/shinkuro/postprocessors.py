@@ -0,0 +1,117 @@
+"""Post-processors applied to rendered prompt text."""
+
+import json
+import re
+from dataclasses import dataclass
+from typing import Callable, Optional
+
+# A post-processor receives the rendered text and its optional parameter
+PostProcessor = Callable[[str, Optional[str]], str]
+
+# Matches `name` or `name(parameter)`
+SPEC_PATTERN = re.compile(
+    r"^\s*([a-zA-Z_][a-zA-Z0-9_]*)\s*(?:\((.*)\))?\s*$", re.DOTALL
+)
+
+_FENCE_RUN = re.compile(r"`{3,}")
+_LINE_BREAK = re.compile(r"\s*\n\s*")
+
+
+@dataclass
+class PostProcessorSpec:
+    """Registered post-processor and its parameter requirements.
+
+    Attributes:
+        func: Function transforming the rendered text
+        takes_param: Whether the processor accepts a parameter
+        requires_param: Whether the parameter is mandatory
+    """
+
+    func: PostProcessor
+    takes_param: bool = False
+    requires_param: bool = False
+
+
+def code_fence(text: str, lang: Optional[str]) -> str:
+    """Wrap text in a fenced code block, longer than any fence inside it."""
+    longest = max((len(run) for run in _FENCE_RUN.findall(text)), default=2)
+    fence = "`" * (longest + 1)
+    return f"{fence}{lang or ''}\n{text}\n{fence}"
+
+
+def json_string(text: str, _: Optional[str]) -> str:
+    """Encode text as a JSON string literal."""
+    return json.dumps(text, ensure_ascii=False)
+
+
+def prefix(text: str, value: Optional[str]) -> str:
+    """Prepend a fixed text."""
+    return f"{value}{text}"
+
+
+def suffix(text: str, value: Optional[str]) -> str:
+    """Append a fixed text."""
+    return f"{text}{value}"
+
+
+def single_line(text: str, _: Optional[str]) -> str:
+    """Collapse newlines and their surrounding whitespace into single spaces."""
+    return _LINE_BREAK.sub(" ", text).strip()
+
+
+POST_PROCESSORS: dict[str, PostProcessorSpec] = {
+    "code_fence": PostProcessorSpec(code_fence, takes_param=True),
+    "json_string": PostProcessorSpec(json_string),
+    "prefix": PostProcessorSpec(prefix, takes_param=True, requires_param=True),
+    "suffix": PostProcessorSpec(suffix, takes_param=True, requires_param=True),
+    "single_line": PostProcessorSpec(single_line),
+}
+
+
+def register_post_processor(name: str, spec: PostProcessorSpec) -> None:
+    """Register a custom post-processor under a name usable in frontmatter."""
+    POST_PROCESSORS[name] = spec
+
+
+def parse_post_processors(
+    specs: list[str],
+) -> list[tuple[PostProcessor, Optional[str]]]:
+    """
+    Parse `post` frontmatter entries into callable post-processors.
+
+    Args:
+        specs: Entries like `json_string` or `code_fence(python)`
+
+    Returns:
+        Post-processors with their parameters, in application order
+
+    Raises:
+        ValueError: If an entry is malformed, unknown, or has bad parameters
+    """
+    processors = []
+    for spec in specs:
+        match = SPEC_PATTERN.match(spec)
+        if not match:
+            raise ValueError(f"Invalid post-processor: {spec!r}")
+
+        name, param = match.group(1), match.group(2)
+        if name not in POST_PROCESSORS:
+            raise ValueError(f"Unknown post-processor: {name}")
+
+        processor = POST_PROCESSORS[name]
+        if param is not None and not processor.takes_param:
+            raise ValueError(f"Post-processor '{name}' does not take a parameter")
+        if not param and processor.requires_param:
+            raise ValueError(f"Post-processor '{name}' requires a parameter")
+
+        processors.append((processor.func, param))
+    return processors
+
+
+def apply_post_processors(
+    text: str, processors: list[tuple[PostProcessor, Optional[str]]]
+) -> str:
+    """Apply post-processors in order, each receiving the previous output."""
+    for func, param in processors:
+        text = func(text, param)
+    return text
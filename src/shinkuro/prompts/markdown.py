@@ -1,13 +1,82 @@
 """Markdown-based prompt implementation."""
 
-from typing import Any
+import re
+from typing import Any, Callable, Optional, cast
 
 from fastmcp.prompts.prompt import Prompt, PromptArgument
-from mcp.types import PromptMessage, TextContent
+from mcp.types import Prompt as MCPPrompt, PromptMessage, TextContent
 from pydantic import Field
 
-from ..model import PromptData
+from ..errors import (
+    ArgumentMismatch,
+    ArgumentsNotAllowed,
+    ArgumentsTooLarge,
+    CyclicReference,
+    InvalidArgumentName,
+    InvalidArgumentType,
+    MissingRequired,
+    UnexpectedArguments,
+)
+from ..model import ROLES, ArgumentType, ListFormat, PromptData, Role
+from ..postprocessors import apply_post_processors, parse_post_processors
+from ..redact import Redactor
+from ..file.read_errors import describe_read_error
 from ..formatters import FormatterInterface, validate_variable_name
+from ..interfaces import DefaultLogger, LoggerInterface
+
+# Maximum depth when resolving defaults that reference other arguments
+MAX_INTERPOLATION_PASSES = 5
+
+# Maximum description length in compact prompts/list entries
+COMPACT_DESCRIPTION_LENGTH = 80
+
+# Textual form of a JSON number, as produced for numeric argument values
+_NUMBER = re.compile(r"^-?\d+(\.\d+)?([eE][+-]?\d+)?$")
+
+# Line switching the role of the following content, see role_marker
+_ROLE_MARKER = re.compile(r"^<!--\s*role:\s*(\S+?)\s*-->[ \t]*$", re.MULTILINE)
+
+
+def split_messages(content: str, default_role: Role) -> list[tuple[Role, str]]:
+    """
+    Split content into messages at <!-- role: X --> marker lines.
+
+    Content before the first marker uses the default role. Blank sections
+    are dropped. Without markers the content is a single message.
+
+    Args:
+        content: Prompt content
+        default_role: Role of content not preceded by a marker
+
+    Returns:
+        List of (role, text) pairs in order
+
+    Raises:
+        ValueError: If a marker names an unknown role
+    """
+    markers = list(_ROLE_MARKER.finditer(content))
+    if not markers:
+        return [(default_role, content)]
+
+    messages: list[tuple[Role, str]] = []
+    role = default_role
+    start = 0
+    for marker in markers:
+        text = content[start : marker.start()].strip("\n")
+        if text.strip():
+            messages.append((role, text))
+        name = marker.group(1)
+        if name not in ROLES:
+            raise ValueError(
+                f"Invalid role '{name}' in message marker, "
+                f"expected one of: {', '.join(ROLES)}"
+            )
+        role = cast(Role, name)
+        start = marker.end()
+    text = content[start:].strip("\n")
+    if text.strip():
+        messages.append((role, text))
+    return messages
 
 
 class MarkdownPrompt(Prompt):
@@ -17,12 +86,43 @@ class MarkdownPrompt(Prompt):
     arg_defaults: dict[str, str] = Field(
         default_factory=dict, description="Default values for arguments"
     )
+    arg_choices: dict[str, list[str]] = Field(
+        default_factory=dict, description="Completion suggestions for arguments"
+    )
+    arg_types: dict[str, ArgumentType] = Field(
+        default_factory=dict, description="Types of non-string arguments"
+    )
+    post: list[str] = Field(
+        default_factory=list,
+        description="Post-processors applied in order to the rendered text",
+    )
+    max_argument_bytes: Optional[int] = Field(
+        default=None,
+        description="Maximum combined byte size of provided argument values",
+    )
+    allow_extra_args: bool = Field(
+        default=False, description="Whether undeclared arguments are ignored"
+    )
+    role: Role = Field(default="user", description="Role of the rendered message")
+    list_format: ListFormat = Field(
+        default=ListFormat.FULL, description="Verbosity of the prompts/list entry"
+    )
 
-    def __init__(self, formatter: FormatterInterface, **data):
+    def __init__(
+        self,
+        formatter: FormatterInterface,
+        redactor: Optional[Redactor] = None,
+        **data,
+    ):
         # Use custom __init__ and private _formatter because Pydantic cannot
         # serialize Protocol types as regular fields
         super().__init__(**data)
         self._formatter = formatter
+        self._redactor = redactor
+        self._reload: Optional[Callable[[], "MarkdownPrompt"]] = None
+        self._mtime: Optional[Callable[[], int]] = None
+        self._loaded_mtime = 0
+        self._logger: LoggerInterface = DefaultLogger()
 
     @classmethod
     def from_prompt_data(
@@ -30,94 +130,274 @@ class MarkdownPrompt(Prompt):
         prompt_data: PromptData,
         formatter: FormatterInterface,
         auto_discover_args: bool = False,
+        max_argument_bytes: Optional[int] = None,
+        redactor: Optional[Redactor] = None,
+        locale: Optional[str] = None,
+        list_format: ListFormat = ListFormat.FULL,
+        allow_extra_args: bool = False,
     ) -> "MarkdownPrompt":
         """Create MarkdownPrompt from PromptData with validation."""
+        # Reject unknown roles in message markers at load time
+        split_messages(prompt_data.content, prompt_data.role)
         if auto_discover_args:
             # Auto-discover arguments from template variables, ignore frontmatter args
             if prompt_data.arguments:
-                raise ValueError(
-                    "prompt_data.arguments must be empty when auto_discover_args is enabled"
-                )
+                raise ArgumentsNotAllowed()
             discovered_args = formatter.extract_arguments(prompt_data.content)
+            arg_defaults = formatter.extract_defaults(prompt_data.content)
             arguments = [
                 PromptArgument(
                     name=arg,
                     description="",
-                    required=True,
+                    required=arg not in arg_defaults,
                 )
                 for arg in sorted(discovered_args)
             ]
-            arg_defaults = {}
         else:
             # Validate arguments
             for arg in prompt_data.arguments:
                 if not validate_variable_name(arg.name):
-                    raise ValueError(
-                        f"Argument name '{arg.name}' contains invalid characters"
-                    )
+                    raise InvalidArgumentName(arg.name)
 
             # Validate content and get discovered arguments
             discovered_args = formatter.extract_arguments(prompt_data.content)
             provided_args = {arg.name for arg in prompt_data.arguments}
 
             if discovered_args != provided_args:
-                raise ValueError(
-                    f"Content arguments {discovered_args} don't match provided arguments {provided_args}"
-                )
+                raise ArgumentMismatch(discovered_args, provided_args)
 
+            # Inline template defaults apply when frontmatter declares none
+            arg_defaults = formatter.extract_defaults(prompt_data.content)
+            arg_defaults.update(
+                {
+                    arg.name: arg.default
+                    for arg in prompt_data.arguments
+                    if arg.default is not None
+                }
+            )
             arguments = [
                 PromptArgument(
                     name=arg.name,
                     description=arg.description,
-                    required=arg.default is None,
+                    required=arg.name not in arg_defaults,
                 )
                 for arg in prompt_data.arguments
             ]
-            arg_defaults = {
-                arg.name: arg.default
-                for arg in prompt_data.arguments
-                if arg.default is not None
-            }
 
         return cls(
             formatter=formatter,
+            redactor=redactor,
             name=prompt_data.name,
             title=prompt_data.title,
-            description=prompt_data.description,
+            description=prompt_data.localized_description(locale),
             arguments=arguments,
             tags={"shinkuro"},
             content=prompt_data.content,
             arg_defaults=arg_defaults,
+            arg_choices={
+                arg.name: arg.choices for arg in prompt_data.arguments if arg.choices
+            },
+            arg_types={
+                arg.name: arg.type
+                for arg in prompt_data.arguments
+                if arg.type != ArgumentType.STRING
+            },
+            post=prompt_data.post,
+            role=prompt_data.role,
+            max_argument_bytes=max_argument_bytes,
+            list_format=list_format,
+            allow_extra_args=allow_extra_args,
         )
 
+    def to_mcp_prompt(self, **overrides: Any) -> MCPPrompt:
+        """Convert to an MCP prompt, omitting details in compact list format."""
+        if not self.title or self.title == self.name:
+            # The title only adds information when it differs from the name
+            overrides = {"title": None, **overrides}
+        if self.list_format == ListFormat.COMPACT:
+            overrides = {
+                "arguments": None,
+                "description": _truncate(self.description),
+                **overrides,
+            }
+        return super().to_mcp_prompt(**overrides)
+
+    def enable_lazy_reload(
+        self,
+        reload: Callable[[], "MarkdownPrompt"],
+        mtime: Callable[[], int],
+        *,
+        logger: LoggerInterface = DefaultLogger(),
+    ) -> None:
+        """
+        Re-create this prompt from its source before rendering if the source changed.
+
+        Args:
+            reload: Builds a fresh prompt from the source file
+            mtime: Returns the current modification time of the source file
+            logger: Logger interface for reload failures
+        """
+        self._reload = reload
+        self._mtime = mtime
+        self._loaded_mtime = mtime()
+        self._logger = logger
+
+    def _reload_if_changed(self) -> None:
+        """Reload prompt content when the source file changed since last load."""
+        if self._reload is None or self._mtime is None:
+            return
+
+        try:
+            current = self._mtime()
+        except OSError as e:
+            self._logger.warning(
+                f"cannot check source of prompt '{self.name}', "
+                f"serving cached version: {describe_read_error(e)}"
+            )
+            return
+        if current == self._loaded_mtime:
+            return
+
+        # Record the new mtime even on failure to avoid re-parsing a broken file
+        self._loaded_mtime = current
+        try:
+            fresh = self._reload()
+        except OSError as e:
+            self._logger.error(
+                f"failed to read source of prompt '{self.name}', "
+                f"serving cached version: {describe_read_error(e)}"
+            )
+            return
+        except Exception as e:
+            self._logger.error(
+                f"failed to reload prompt '{self.name}', serving cached version: {e}"
+            )
+            return
+
+        self._formatter = fresh._formatter
+        self.title = fresh.title
+        self.description = fresh.description
+        self.arguments = fresh.arguments
+        self.content = fresh.content
+        self.arg_defaults = fresh.arg_defaults
+        self.arg_choices = fresh.arg_choices
+        self.arg_types = fresh.arg_types
+        self.post = fresh.post
+        self.role = fresh.role
+
     async def render(
         self, arguments: dict[str, Any] | None = None
     ) -> list[PromptMessage]:
         """Render the prompt with variable substitution."""
+        self._reload_if_changed()
+        self._check_argument_size(arguments)
         self._validate_arguments(arguments)
+        self._check_argument_types(arguments)
 
         # Merge provided arguments with defaults
         render_args = self.arg_defaults.copy()
         if arguments:
             render_args.update(arguments)
+        render_args = self._interpolate_defaults(render_args, set(arguments or {}))
 
-        # Perform variable substitution using formatter
-        content = self._formatter.format(self.content, render_args)
-
-        return [
-            PromptMessage(
-                role="user",
-                content=TextContent(type="text", text=content),
+        # Perform variable substitution per message using formatter
+        post = parse_post_processors(self.post)
+        messages = []
+        for role, text in split_messages(self.content, self.role):
+            text = self._formatter.format(text, render_args)
+            if self._redactor:
+                text = self._redactor.redact(text)
+            messages.append(
+                PromptMessage(
+                    role=role,
+                    content=TextContent(
+                        type="text", text=apply_post_processors(text, post)
+                    ),
+                )
             )
-        ]
+        return messages
 
-    def _validate_arguments(self, arguments: dict[str, Any] | None) -> None:
-        """Validate that all required arguments are provided."""
-        if not self.arguments:
+    def _interpolate_defaults(
+        self, render_args: dict[str, Any], provided: set[str]
+    ) -> dict[str, Any]:
+        """
+        Resolve defaults that reference other arguments, each formatted once.
+
+        Referenced defaults are resolved first, so escapes in a default are
+        unescaped exactly once and substituted values are never re-parsed.
+        """
+        pending = {name for name in self.arg_defaults if name not in provided}
+        resolved = dict(render_args)
+        done: set[str] = set()
+
+        def resolve(name: str, chain: list[str]) -> None:
+            if name in done:
+                return
+            if name in chain:
+                raise CyclicReference(
+                    chain[chain.index(name) :], MAX_INTERPOLATION_PASSES
+                )
+            if len(chain) >= MAX_INTERPOLATION_PASSES:
+                raise CyclicReference(chain, MAX_INTERPOLATION_PASSES)
+            references = self._references(resolved[name], resolved)
+            if references is not None:
+                for reference in sorted(references & pending):
+                    resolve(reference, chain + [name])
+                resolved[name] = self._formatter.format(resolved[name], resolved)
+            done.add(name)
+
+        for name in sorted(pending):
+            resolve(name, [])
+        return resolved
+
+    def _references(self, value: Any, known: dict[str, Any]) -> set[str] | None:
+        """Return argument names referenced by a default, None if it is literal."""
+        if not isinstance(value, str):
+            return None
+        try:
+            references = self._formatter.extract_arguments(value)
+        except ValueError:
+            # Not a valid template, treat the value as literal text
+            return None
+        return references if references <= known.keys() else None
+
+    def _check_argument_size(self, arguments: dict[str, Any] | None) -> None:
+        """Reject requests whose argument values are too large in total."""
+        if self.max_argument_bytes is None or not arguments:
             return
 
-        required = {arg.name for arg in self.arguments if arg.required}
+        total = sum(len(str(value).encode("utf-8")) for value in arguments.values())
+        if total > self.max_argument_bytes:
+            raise ArgumentsTooLarge(total, self.max_argument_bytes)
+
+    def _validate_arguments(self, arguments: dict[str, Any] | None) -> None:
+        """Validate that all required and no undeclared arguments are provided."""
+        declared = {arg.name for arg in self.arguments or []}
         provided = set(arguments or {})
+        unexpected = provided - declared
+        if unexpected and not self.allow_extra_args:
+            raise UnexpectedArguments(unexpected)
+
+        required = {arg.name for arg in self.arguments or [] if arg.required}
         missing = required - provided
         if missing:
-            raise ValueError(f"Missing required arguments: {missing}")
+            raise MissingRequired(missing)
+
+    def _check_argument_types(self, arguments: dict[str, str] | None) -> None:
+        """Reject provided values that do not match their declared type."""
+        for name, value in (arguments or {}).items():
+            arg_type = self.arg_types.get(name)
+            if arg_type == ArgumentType.NUMBER and not _NUMBER.match(value):
+                raise InvalidArgumentType(name, "number", value)
+            if arg_type == ArgumentType.BOOLEAN and value not in ("true", "false"):
+                raise InvalidArgumentType(name, "boolean", value)
+
+
+def _truncate(description: Optional[str]) -> Optional[str]:
+    """Shorten a description to its first line within the compact length."""
+    if not description:
+        return description
+    line = description.splitlines()[0]
+    if len(line) <= COMPACT_DESCRIPTION_LENGTH:
+        return line
+    return line[: COMPACT_DESCRIPTION_LENGTH - 3].rstrip() + "..."
@@ -0,0 +1,61 @@
+"""Redaction of secret-looking content in rendered prompts."""
+
+import re
+from typing import Iterable
+
+from .interfaces import DefaultLogger, LoggerInterface
+from .model import PromptData
+
+REDACTED = "***"
+
+# Built-in patterns for commonly leaked credentials
+BUILTIN_PATTERNS = [
+    r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",  # AWS access key ID
+    r"\bgh[pousr]_[A-Za-z0-9]{36,}\b",  # GitHub token
+    r"\bgithub_pat_[A-Za-z0-9_]{22,}\b",  # GitHub fine-grained token
+    r"\bxox[abposr]-[A-Za-z0-9-]{10,}\b",  # Slack token
+    r"\bglpat-[A-Za-z0-9_-]{20,}\b",  # GitLab personal access token
+    r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+]
+
+
+class Redactor:
+    """Replaces matches of secret patterns with a placeholder."""
+
+    def __init__(self, patterns: Iterable[str] = (), builtins: bool = False):
+        sources = list(patterns)
+        if builtins:
+            sources += BUILTIN_PATTERNS
+
+        self.patterns: list[re.Pattern[str]] = []
+        for pattern in sources:
+            try:
+                self.patterns.append(re.compile(pattern))
+            except re.error as e:
+                raise ValueError(f"Invalid redact pattern '{pattern}': {e}")
+
+    def redact(self, text: str) -> str:
+        """Replace every match of every pattern with the redaction marker."""
+        for pattern in self.patterns:
+            text = pattern.sub(REDACTED, text)
+        return text
+
+    def find(self, text: str) -> list[str]:
+        """Return the patterns that match the text."""
+        return [pattern.pattern for pattern in self.patterns if pattern.search(text)]
+
+
+def warn_secrets(
+    prompt_data: PromptData,
+    redactor: Redactor,
+    *,
+    logger: LoggerInterface = DefaultLogger(),
+) -> None:
+    """Warn when prompt source content contains text that will be redacted."""
+    matched = redactor.find(prompt_data.content)
+    if matched:
+        source = prompt_data.path or prompt_data.name
+        logger.warning(
+            f"prompt '{prompt_data.name}' in {source} contains content matching "
+            f"redact patterns {matched}, it will be replaced with '{REDACTED}'"
+        )
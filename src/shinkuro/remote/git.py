@@ -1,45 +1,201 @@
 """Git repository cloning and caching."""
 
+import re
 from pathlib import Path
-from giturlparse import parse
-from ..interfaces import GitInterface, DefaultGit
+from typing import Iterator, List, Optional, Tuple
+from urllib.parse import quote, urlsplit
+from ..interfaces import GitInterface, DefaultGit, LoggerInterface, DefaultLogger
+from .lock import LOCK_TIMEOUT, file_lock
 
+# First line of every Git LFS pointer file
+LFS_POINTER_PREFIX = b"version https://git-lfs.github.com/spec/v1"
 
-def get_local_cache_path(git_url: str, cache_dir: Path) -> Path:
+# Pointer files are small text files, skip anything larger when scanning
+LFS_POINTER_MAX_SIZE = 1024
+
+# Owner and repository names become cache directory names, e.g. ~user/repo
+_SAFE_PATH_COMPONENT = re.compile(r"^[\w.~-]+$")
+
+
+# Joins nested owner groups into one cache directory name, it is not allowed
+# in components, so group/sub/repo cannot collide with the repository group/sub
+_OWNER_SEPARATOR = "+"
+
+
+# scp-like syntax without a scheme, e.g. git@github.com:owner/repo.git
+_SCP_LIKE_URL = re.compile(r"^(?:[^@/]+@)?[^/:]+:(?P<path>.+)$")
+
+
+def _is_safe_path_component(value: str) -> bool:
+    """Check that a URL component cannot escape its parent directory."""
+    return bool(_SAFE_PATH_COMPONENT.match(value)) and value not in (".", "..")
+
+
+def parse_git_url(git_url: str) -> Tuple[List[str], str]:
+    """
+    Split a git URL into its owner path and repository name.
+
+    The last path component is the repository name and everything before it
+    the owner, so nested groups like gitlab.com/group/subgroup/repo keep all
+    their components. URLs with a scheme may include credentials and a port,
+    and file:// URLs use their local path.
+
+    Args:
+        git_url: Git repository URL
+
+    Returns:
+        Owner path components and repository name without .git
+
+    Raises:
+        ValueError: If the URL has no owner and repository name, or a
+            component is unsafe as a directory name
+    """
+    if "://" in git_url:
+        path = urlsplit(git_url).path
+    else:
+        match = _SCP_LIKE_URL.match(git_url)
+        path = match.group("path") if match else ""
+
+    parts = [part for part in path.split("/") if part]
+    if len(parts) < 2:
+        raise ValueError(f"Cannot extract user/repo from git URL: {git_url}")
+    *owner, name = parts
+    name = name.removesuffix(".git")
+    for component in [*owner, name]:
+        if not _is_safe_path_component(component):
+            raise ValueError(
+                f"Unsafe path component '{component}' in git URL: {git_url}"
+            )
+    return owner, name
+
+
+def get_local_cache_path(
+    git_url: str, cache_dir: Path, ref: Optional[str] = None
+) -> Path:
     """
     Get the local cache path for a git repository.
 
+    Each ref is cached separately, so different refs of the same repository
+    do not overwrite each other. Nested owner groups share one directory,
+    e.g. gitlab.com/group/sub/repo is cached in git/group+sub/repo.
+
     Args:
         git_url: Git repository URL
         cache_dir: Base cache directory
+        ref: Branch, tag or commit to check out, default branch if not given
 
     Returns:
         Local path where the repository would be cached
+
+    Raises:
+        ValueError: If owner and name cannot be extracted or are unsafe as
+            directory names
     """
-    parsed = parse(git_url)
-    owner = getattr(parsed, "owner", None)
-    name = getattr(parsed, "name", None)
+    owner, name = parse_git_url(git_url)
+    owner_path = cache_dir / "git" / _OWNER_SEPARATOR.join(owner)
+    if ref:
+        return owner_path / f"{name}@{_encode_ref(ref)}"
+    return owner_path / name
 
-    if not owner or not name:
-        raise ValueError(f"Cannot extract user/repo from git URL: {git_url}")
 
-    return cache_dir / "git" / str(owner) / str(name)
+def _encode_ref(ref: str) -> str:
+    """
+    Make a ref usable as a single path component.
+
+    Percent-encoding keeps distinct refs apart, e.g. feature/x becomes
+    feature%2Fx and does not share a checkout with feature_x.
+    """
+    return quote(ref, safe="")
 
 
 def clone_or_update_repo(
-    git_url: str, local_path: Path, auto_pull: bool, *, git: GitInterface = DefaultGit()
+    git_url: str,
+    local_path: Path,
+    auto_pull: bool,
+    *,
+    ref: Optional[str] = None,
+    git: GitInterface = DefaultGit(),
+    lock_timeout: float = LOCK_TIMEOUT,
+    logger: LoggerInterface = DefaultLogger(),
 ) -> None:
     """
     Clone or update a git repository at the specified local path.
 
+    Concurrent calls for the same path, including from other processes, are
+    serialized with a lock file next to the repository.
+
     Args:
         git_url: Git repository URL
         local_path: Local path to clone/update the repository
-        auto_pull: Whether to pull latest changes if repo exists
+        auto_pull: Whether to pull latest changes if repo exists, a no-op when
+            ref is a tag or commit
+        ref: Branch, tag or commit to check out, default branch if not given
         git: Git interface for git operations
+        lock_timeout: Seconds to wait for another clone/update to finish
+        logger: Logger interface for progress messages
+
+    Raises:
+        TimeoutError: If the lock could not be acquired within lock_timeout
     """
-    if local_path.exists():
-        if auto_pull:
-            git.pull(local_path)
-    else:
-        git.clone(git_url, local_path)
+    with file_lock(local_path.with_name(f"{local_path.name}.lock"), lock_timeout):
+        if local_path.exists():
+            if not auto_pull:
+                logger.debug(f"using cached repository {local_path}")
+            elif not ref or git.on_branch(local_path):
+                logger.info(f"pulling repository {local_path}")
+                git.pull(local_path)
+            else:
+                logger.debug(f"not pulling {local_path}, '{ref}' is not a branch")
+        else:
+            logger.info(f"cloning repository into {local_path}")
+            git.clone(git_url, local_path, ref)
+
+
+def find_lfs_pointers(local_path: Path) -> Iterator[Path]:
+    """
+    Find Git LFS pointer files in a repository working tree.
+
+    Args:
+        local_path: Local path of the repository
+
+    Yields:
+        Paths of files that are LFS pointers instead of real content
+    """
+    for path in local_path.rglob("*"):
+        if ".git" in path.relative_to(local_path).parts or not path.is_file():
+            continue
+        if path.stat().st_size > LFS_POINTER_MAX_SIZE:
+            continue
+        with open(path, "rb") as f:
+            if f.read(len(LFS_POINTER_PREFIX)) == LFS_POINTER_PREFIX:
+                yield path
+
+
+def fetch_lfs_objects(
+    local_path: Path,
+    *,
+    git: GitInterface = DefaultGit(),
+    logger: LoggerInterface = DefaultLogger(),
+) -> None:
+    """
+    Replace Git LFS pointer files with their content.
+
+    Args:
+        local_path: Local path of the repository
+        git: Git interface for git operations
+        logger: Logger interface for warning messages
+    """
+    if git.lfs_available():
+        try:
+            git.lfs_pull(local_path)
+        except Exception as e:
+            logger.error(f"failed to pull Git LFS objects in {local_path}: {e}")
+        return
+
+    pointers = list(find_lfs_pointers(local_path))
+    if pointers:
+        logger.warning(
+            f"found {len(pointers)} Git LFS pointer file(s) in {local_path} "
+            f"(e.g. {pointers[0].relative_to(local_path)}) but git-lfs is not installed, "
+            "install git-lfs to fetch their content"
+        )
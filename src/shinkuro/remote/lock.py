@@ -0,0 +1,77 @@
+"""Inter-process file lock for the remote repository cache."""
+
+import os
+import sys
+import time
+from contextlib import contextmanager
+from pathlib import Path
+from typing import IO, Iterator
+
+if sys.platform == "win32":
+    import msvcrt
+
+    def _try_lock(f: IO[bytes]) -> bool:
+        # msvcrt locks bytes from the current position, which is the end of
+        # the file after opening in append mode or writing the pid
+        f.seek(0)
+        try:
+            msvcrt.locking(f.fileno(), msvcrt.LK_NBLCK, 1)
+            return True
+        except OSError:
+            return False
+
+    def _unlock(f: IO[bytes]) -> None:
+        f.seek(0)
+        msvcrt.locking(f.fileno(), msvcrt.LK_UNLCK, 1)
+
+else:
+    import fcntl
+
+    def _try_lock(f: IO[bytes]) -> bool:
+        try:
+            fcntl.flock(f.fileno(), fcntl.LOCK_EX | fcntl.LOCK_NB)
+            return True
+        except OSError:
+            return False
+
+    def _unlock(f: IO[bytes]) -> None:
+        fcntl.flock(f.fileno(), fcntl.LOCK_UN)
+
+
+# Seconds to wait for another instance to finish cloning/updating
+LOCK_TIMEOUT = 300.0
+
+
+@contextmanager
+def file_lock(
+    path: Path, timeout: float = LOCK_TIMEOUT, poll_interval: float = 0.1
+) -> Iterator[None]:
+    """
+    Hold an exclusive lock on a lock file while the block runs.
+
+    Args:
+        path: Lock file path, created if missing
+        timeout: Seconds to wait before giving up
+        poll_interval: Seconds between lock attempts
+
+    Raises:
+        TimeoutError: If the lock could not be acquired within the timeout
+    """
+    path.parent.mkdir(parents=True, exist_ok=True)
+    with open(path, "a+b") as f:
+        deadline = time.monotonic() + timeout
+        while not _try_lock(f):
+            if time.monotonic() >= deadline:
+                raise TimeoutError(
+                    f"Timed out after {timeout:g}s waiting for lock {path}, "
+                    "another shinkuro instance is still updating the cache"
+                )
+            time.sleep(poll_interval)
+        try:
+            f.seek(0)
+            f.truncate()
+            f.write(str(os.getpid()).encode())
+            f.flush()
+            yield
+        finally:
+            _unlock(f)
@@ -0,0 +1,124 @@
+"""Render a prompt from the command line for local testing."""
+
+import asyncio
+from pathlib import Path
+from typing import Dict, Iterable, List
+
+import typer
+from mcp.types import TextContent
+
+from .file.scan import scan_markdown_folders
+from .prompts.markdown import MarkdownPrompt
+from .remote.git import get_local_cache_path
+
+
+class _QuietLogger:
+    """Logger discarding messages, which would garble shell completion."""
+
+    def error(self, message: str) -> None:
+        pass
+
+    def warning(self, message: str) -> None:
+        pass
+
+    def info(self, message: str) -> None:
+        pass
+
+    def debug(self, message: str) -> None:
+        pass
+
+
+def parse_render_args(values: Iterable[str]) -> Dict[str, str]:
+    """
+    Parse key=value arguments passed to a rendered prompt.
+
+    Args:
+        values: Arguments like topic=testing, the value may contain '='
+
+    Returns:
+        Argument values keyed by name, later values winning
+
+    Raises:
+        ValueError: If an argument is not in key=value form
+    """
+    arguments = {}
+    for value in values:
+        key, sep, arg_value = value.partition("=")
+        key = key.strip()
+        if not sep or not key:
+            raise ValueError(f"Invalid argument '{value}', expected key=value")
+        arguments[key] = arg_value
+    return arguments
+
+
+def render_prompt(prompt: MarkdownPrompt, arguments: Dict[str, str]) -> str:
+    """
+    Render a prompt the same way prompts/get does and join its messages.
+
+    A single message is returned as is. With several messages each one is
+    preceded by a line naming its role.
+
+    Args:
+        prompt: Prompt to render
+        arguments: Argument values keyed by name
+
+    Returns:
+        Rendered text
+
+    Raises:
+        PromptError: If the arguments are missing, unexpected or invalid
+    """
+    messages = asyncio.run(prompt.render(arguments))
+    parts = [
+        (message.role, message.content.text)
+        for message in messages
+        if isinstance(message.content, TextContent)
+    ]
+    if len(parts) == 1:
+        return parts[0][1]
+    return "\n\n".join(f"--- {role} ---\n{text}" for role, text in parts)
+
+
+def complete_prompt_names(ctx: typer.Context, incomplete: str) -> List[str]:
+    """
+    Complete --render with the names of prompts in the configured folders.
+
+    Runs on every completion request, so a git repository is only read if
+    it is already cached and nothing is cloned or pulled.
+
+    Args:
+        ctx: Context holding the options typed so far
+        incomplete: Partial prompt name typed so far
+
+    Returns:
+        Matching prompt names, sorted
+    """
+    params = ctx.params
+    folders = list(params.get("folder") or [])
+    try:
+        if params.get("git_url"):
+            repo_path = get_local_cache_path(
+                params["git_url"],
+                Path(params.get("cache_dir") or "~/.shinkuro/remote").expanduser(),
+                params.get("git_ref"),
+            )
+            roots = [repo_path / folder for folder in folders] or [repo_path]
+        else:
+            roots = [Path(folder) for folder in folders]
+        sources = scan_markdown_folders(
+            roots,
+            bool(params.get("skip_frontmatter")),
+            include_hidden=bool(params.get("include_hidden")),
+            namespace_separator=(
+                params.get("namespace_separator")
+                if params.get("namespace_by_dir")
+                else None
+            ),
+            strict_frontmatter=bool(params.get("strict_frontmatter")),
+            include=params.get("include"),
+            logger=_QuietLogger(),
+        )
+    except (ValueError, OSError):
+        return []
+    names = {prompt_data.name for _, prompt_data in sources}
+    return sorted(name for name in names if name.startswith(incomplete))
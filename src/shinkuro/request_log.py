@@ -0,0 +1,35 @@
+"""Per-request logging of handled MCP requests."""
+
+import time
+from typing import Any, Callable
+
+from fastmcp.server.middleware import CallNext, Middleware, MiddlewareContext
+
+from .interfaces import DefaultLogger, LoggerInterface
+from .timing import format_duration
+
+
+class RequestLogMiddleware(Middleware):
+    """Log the method, prompt name and duration of each request at debug level."""
+
+    def __init__(
+        self,
+        logger: LoggerInterface = DefaultLogger(),
+        clock: Callable[[], float] = time.perf_counter,
+    ):
+        self._logger = logger
+        self._clock = clock
+
+    async def on_request(self, context: MiddlewareContext, call_next: CallNext) -> Any:
+        start = self._clock()
+        outcome = "failed"
+        try:
+            result = await call_next(context)
+            outcome = "handled"
+            return result
+        finally:
+            # Prompt requests carry the prompt name in their params
+            name = getattr(context.message, "name", None)
+            target = f" '{name}'" if isinstance(name, str) else ""
+            elapsed = format_duration(self._clock() - start)
+            self._logger.debug(f"{outcome} {context.method}{target} in {elapsed}")
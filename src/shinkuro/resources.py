@@ -0,0 +1,132 @@
+"""MCP resources exposing prompt sources."""
+
+from pathlib import Path
+from typing import Callable, Iterable, Mapping, Optional
+
+from fastmcp import FastMCP
+from fastmcp.exceptions import ResourceError
+
+from .file.read_errors import describe_read_error
+from .interfaces import DefaultFileSystem, FileSystemInterface
+from .model import PromptData
+from .prompts.markdown import MarkdownPrompt
+from .redact import Redactor
+
+# URI template addressing the unrendered source of a prompt by name, the
+# wildcard lets namespaced names like coding/review span path segments
+PROMPT_URI_TEMPLATE = "shinkuro://prompt/{name*}"
+
+
+def read_prompt_source(
+    prompts: Mapping[str, MarkdownPrompt],
+    name: str,
+    redactor: Optional[Redactor] = None,
+) -> str:
+    """
+    Get the template content of a prompt without rendering it.
+
+    Args:
+        prompts: Served prompts keyed by name
+        name: Prompt name from the resource URI
+        redactor: Redactor applied to the content, if any
+
+    Returns:
+        Template content of the prompt
+
+    Raises:
+        ResourceError: If no prompt has the given name
+    """
+    prompt = prompts.get(name)
+    if prompt is None:
+        raise ResourceError(f"Unknown prompt: {name}")
+    return redactor.redact(prompt.content) if redactor else prompt.content
+
+
+def register_prompt_resources(
+    mcp: FastMCP,
+    prompts: Mapping[str, MarkdownPrompt],
+    redactor: Optional[Redactor] = None,
+) -> None:
+    """
+    Register a resource template that reads prompt sources by name.
+
+    Args:
+        mcp: Server to register the template with
+        prompts: Served prompts keyed by name
+        redactor: Redactor applied to the content, if any
+    """
+
+    @mcp.resource(
+        PROMPT_URI_TEMPLATE,
+        name="prompt",
+        description="Template source of a prompt, before argument substitution",
+        mime_type="text/markdown",
+    )
+    def prompt_source(name: str) -> str:
+        return read_prompt_source(prompts, name, redactor)
+
+
+def read_prompt_file(
+    path: Path,
+    redactor: Optional[Redactor] = None,
+    fs: FileSystemInterface = DefaultFileSystem(),
+) -> str:
+    """
+    Read the raw markdown of a prompt file, frontmatter included.
+
+    Args:
+        path: Prompt file found by the scan
+        redactor: Redactor applied to the content, if any
+        fs: File system interface for file operations
+
+    Returns:
+        Current content of the file
+
+    Raises:
+        ResourceError: If the file cannot be read
+    """
+    try:
+        content = fs.read_text(path)
+    except OSError as e:
+        raise ResourceError(f"Cannot read {path}: {describe_read_error(e)}") from e
+    return redactor.redact(content) if redactor else content
+
+
+def register_file_resources(
+    mcp: FastMCP,
+    prompts: Iterable[PromptData],
+    redactor: Optional[Redactor] = None,
+    fs: FileSystemInterface = DefaultFileSystem(),
+) -> None:
+    """
+    Register the source file of each prompt as a file:// resource.
+
+    Only files found by the scan are registered, so a URI of any other file
+    is an unknown resource and never reaches the file system.
+
+    Args:
+        mcp: Server to register the resources with
+        prompts: Served prompts with the files they were loaded from
+        redactor: Redactor applied to the content, if any
+        fs: File system interface for file operations
+    """
+    for prompt_data in prompts:
+        if prompt_data.path is None:
+            continue
+        mcp.resource(
+            prompt_data.path.resolve().as_uri(),
+            name=prompt_data.name,
+            description=f"Source file of prompt '{prompt_data.name}'",
+            mime_type="text/markdown",
+        )(_file_reader(prompt_data.path, redactor, fs))
+
+
+def _file_reader(
+    path: Path, redactor: Optional[Redactor], fs: FileSystemInterface
+) -> Callable[[], str]:
+    """Bind a prompt file to a resource function, one per registered URI."""
+
+    def read_file() -> str:
+        return read_prompt_file(path, redactor, fs)
+
+    return read_file
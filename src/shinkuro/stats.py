@@ -0,0 +1,54 @@
+"""Argument usage statistics for prompt authors."""
+
+from typing import Any, Iterable
+
+from .formatters import FormatterInterface, resolve_formatter
+from .model import PromptData
+
+
+def collect_argument_usage(
+    prompts: Iterable[PromptData], formatter: FormatterInterface
+) -> dict[str, Any]:
+    """
+    Compare declared arguments with the variables referenced in each prompt.
+
+    Args:
+        prompts: Prompt data to analyze
+        formatter: Formatter for prompts without their own variable format
+
+    Returns:
+        Per-prompt usage report and aggregate counts across the catalog
+    """
+    report = []
+    total_declared = 0
+    total_unused = 0
+
+    for prompt_data in prompts:
+        declared = [arg.name for arg in prompt_data.arguments]
+        entry: dict[str, Any] = {"name": prompt_data.name, "declared": declared}
+        try:
+            used = resolve_formatter(prompt_data, formatter).extract_arguments(
+                prompt_data.content
+            )
+        except ValueError as e:
+            entry["error"] = str(e)
+            report.append(entry)
+            continue
+
+        entry["used"] = sorted(used)
+        entry["unused"] = [name for name in declared if name not in used]
+        entry["undeclared"] = sorted(used - set(declared))
+        report.append(entry)
+
+        total_declared += len(declared)
+        total_unused += len(entry["unused"])
+
+    return {
+        "prompts": report,
+        "totals": {
+            "prompts": len(report),
+            "prompts_with_unused": sum(1 for entry in report if entry.get("unused")),
+            "declared_arguments": total_declared,
+            "unused_arguments": total_unused,
+        },
+    }
@@ -0,0 +1,110 @@
+"""Startup phase timing for diagnosing slow boots."""
+
+import threading
+import time
+from contextlib import contextmanager
+from dataclasses import dataclass
+from typing import Callable, Dict, Iterator, List
+
+
+@dataclass
+class Phase:
+    """Aggregated timing of one startup phase."""
+
+    name: str
+    unit: str = "items"
+    seconds: float = 0.0
+    count: int = 0
+    bytes: int = 0
+    spans: int = 0
+
+
+class Span:
+    """Counters for a single in-flight span, merged into its phase on exit."""
+
+    def __init__(self):
+        self.count = 0
+        self.bytes = 0
+
+    def add(self, count: int = 1, nbytes: int = 0) -> None:
+        """Record processed items and bytes."""
+        self.count += count
+        self.bytes += nbytes
+
+
+class TimingCollector:
+    """
+    Collect wall time, item counts and bytes per startup phase.
+
+    Spans with the same name are aggregated, so a phase that runs in several
+    threads reports the sum of its spans.
+    """
+
+    def __init__(self, clock: Callable[[], float] = time.perf_counter):
+        self._clock = clock
+        self._phases: Dict[str, Phase] = {}
+        self._lock = threading.Lock()
+
+    @contextmanager
+    def span(self, name: str, unit: str = "items") -> Iterator[Span]:
+        """
+        Time a block of work as part of the named phase.
+
+        Args:
+            name: Phase name
+            unit: Label for the item count in the summary
+
+        Yields:
+            Span whose counters are added to the phase when the block exits
+        """
+        span = Span()
+        start = self._clock()
+        try:
+            yield span
+        finally:
+            elapsed = self._clock() - start
+            with self._lock:
+                phase = self._phases.setdefault(name, Phase(name, unit))
+                phase.seconds += elapsed
+                phase.count += span.count
+                phase.bytes += span.bytes
+                phase.spans += 1
+
+    @property
+    def phases(self) -> List[Phase]:
+        """Phases in the order they were first completed."""
+        with self._lock:
+            return list(self._phases.values())
+
+    def summary(self) -> str:
+        """Format all phases as a single line."""
+        return ", ".join(format_phase(phase) for phase in self.phases)
+
+
+def format_duration(seconds: float) -> str:
+    """Format a duration as milliseconds below one second, seconds otherwise."""
+    if seconds < 1:
+        return f"{seconds * 1000:.0f}ms"
+    return f"{seconds:.1f}s"
+
+
+def format_bytes(nbytes: int) -> str:
+    """Format a byte count with a binary unit."""
+    if nbytes < 1024:
+        return f"{nbytes}B"
+    if nbytes < 1024 * 1024:
+        return f"{nbytes / 1024:.0f}KB"
+    return f"{nbytes / (1024 * 1024):.0f}MB"
+
+
+def format_phase(phase: Phase) -> str:
+    """Format a phase like ``scan 412ms (3,214 files, 48MB)``."""
+    text = f"{phase.name} {format_duration(phase.seconds)}"
+    details = []
+    if phase.count:
+        details.append(f"{phase.count:,} {phase.unit}")
+    if phase.bytes:
+        details.append(format_bytes(phase.bytes))
+    if details:
+        text += f" ({', '.join(details)})"
+    return text
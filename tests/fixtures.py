@@ -1,16 +1,24 @@
 """Test fixtures and factories for creating test data."""
 
 from pathlib import Path
-from shinkuro.model import Argument, PromptData
+from shinkuro.model import Argument, ArgumentType, PromptData
 
 
 def create_argument(
     name: str = "test_arg",
     description: str = "Test argument",
     default: str | None = None,
+    choices: list[str] | None = None,
+    arg_type: ArgumentType = ArgumentType.STRING,
 ) -> Argument:
     """Create a test Argument instance."""
-    return Argument(name=name, description=description, default=default)
+    return Argument(
+        name=name,
+        description=description,
+        default=default,
+        choices=choices or [],
+        type=arg_type,
+    )
 
 
 def create_prompt_data(
@@ -19,6 +27,7 @@ def create_prompt_data(
     description: str = "Test description",
     arguments: list[Argument] | None = None,
     content: str = "Test content",
+    path: Path | None = None,
 ) -> PromptData:
     """Create a test PromptData instance."""
     return PromptData(
@@ -27,6 +36,7 @@ def create_prompt_data(
         description=description,
         arguments=arguments or [],
         content=content,
+        path=path,
     )
 
 
@@ -1,7 +1,7 @@
 """Shared mock implementations for testing."""
 
 from pathlib import Path
-from typing import Iterator
+from typing import Iterator, Optional
 
 
 class MockFileSystem:
@@ -9,6 +9,7 @@ class MockFileSystem:
 
     def __init__(self, files: dict[Path, str]):
         self.files = files
+        self.mtimes: dict[Path, int] = {}
 
     def read_text(self, path: Path) -> str:
         return self.files[path]
@@ -22,26 +23,53 @@ class MockFileSystem:
     def is_dir(self, path: Path) -> bool:
         return path == Path("/test")
 
+    def mtime(self, path: Path) -> int:
+        return self.mtimes.get(path, 0)
+
 
 class MockLogger:
     """Mock logger for testing."""
 
     def __init__(self):
+        self.errors = []
         self.warnings = []
+        self.infos = []
+        self.debugs = []
+
+    def error(self, message: str) -> None:
+        self.errors.append(message)
 
     def warning(self, message: str) -> None:
         self.warnings.append(message)
 
+    def info(self, message: str) -> None:
+        self.infos.append(message)
+
+    def debug(self, message: str) -> None:
+        self.debugs.append(message)
+
 
 class MockGit:
     """Mock git interface for testing."""
 
-    def __init__(self):
+    def __init__(self, lfs_installed: bool = True, detached: bool = False):
         self.cloned = []
         self.pulled = []
+        self.detached = detached
+        self.lfs_installed = lfs_installed
+        self.lfs_pulled = []
 
-    def clone(self, url: str, path: Path) -> None:
-        self.cloned.append({"url": url, "path": path})
+    def clone(self, url: str, path: Path, ref: Optional[str] = None) -> None:
+        self.cloned.append({"url": url, "path": path, "ref": ref})
 
     def pull(self, path: Path) -> None:
         self.pulled.append(path)
+
+    def on_branch(self, path: Path) -> bool:
+        return not self.detached
+
+    def lfs_available(self) -> bool:
+        return self.lfs_installed
+
+    def lfs_pull(self, path: Path) -> None:
+        self.lfs_pulled.append(path)
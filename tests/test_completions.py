@@ -0,0 +1,88 @@
+"""Tests for completions.py module."""
+
+import pytest
+from fastmcp import FastMCP
+from mcp.types import (
+    CompleteRequest,
+    CompleteRequestParams,
+    CompletionArgument,
+    PromptReference,
+    ResourceTemplateReference,
+)
+from shinkuro.completions import (
+    MAX_COMPLETION_VALUES,
+    complete_argument,
+    register_completions,
+)
+from shinkuro.formatters import BraceFormatter
+from shinkuro.prompts.markdown import MarkdownPrompt
+from .fixtures import create_argument, create_prompt_data
+
+
+def _prompts(choices: list[str]) -> dict[str, MarkdownPrompt]:
+    prompt_data = create_prompt_data(
+        name="review",
+        arguments=[
+            create_argument("language", "Language", None, choices),
+            create_argument("code", "Code to review", None),
+        ],
+        content="Review this {language} code: {code}",
+    )
+    return {"review": MarkdownPrompt.from_prompt_data(prompt_data, BraceFormatter())}
+
+
+def test_complete_argument_filters_by_prefix():
+    prompts = _prompts(["python", "Perl", "rust"])
+
+    completion = complete_argument(prompts, "review", "language", "p")
+
+    assert completion.values == ["python", "Perl"]
+    assert completion.total == 2
+    assert completion.hasMore is False
+
+
+def test_complete_argument_empty_value_lists_all_choices():
+    prompts = _prompts(["python", "rust"])
+
+    completion = complete_argument(prompts, "review", "language", "")
+
+    assert completion.values == ["python", "rust"]
+
+
+@pytest.mark.parametrize(
+    "name, argument",
+    [("review", "code"), ("review", "missing"), ("missing", "language")],
+)
+def test_complete_argument_without_choices_is_empty(name, argument):
+    completion = complete_argument(_prompts(["python"]), name, argument, "")
+    assert completion.values == []
+    assert completion.total == 0
+
+
+def test_complete_argument_limits_values():
+    choices = [f"lang{i}" for i in range(MAX_COMPLETION_VALUES + 5)]
+
+    completion = complete_argument(_prompts(choices), "review", "language", "lang")
+
+    assert len(completion.values) == MAX_COMPLETION_VALUES
+    assert completion.total == MAX_COMPLETION_VALUES + 5
+    assert completion.hasMore is True
+
+
+def _request(ref: PromptReference | ResourceTemplateReference) -> CompleteRequest:
+    argument = CompletionArgument(name="language", value="ru")
+    return CompleteRequest(params=CompleteRequestParams(ref=ref, argument=argument))
+
+
+@pytest.mark.asyncio
+async def test_register_completions_handles_prompt_references():
+    mcp = FastMCP(name="test")
+    register_completions(mcp, _prompts(["python", "rust"]))
+    handler = mcp._mcp_server.request_handlers[CompleteRequest]
+
+    result = await handler(_request(PromptReference(type="ref/prompt", name="review")))
+    assert result.root.completion.values == ["rust"]
+
+    template = ResourceTemplateReference(type="ref/resource", uri="shinkuro://x")
+    result = await handler(_request(template))
+    assert result.root.completion.values == []
@@ -0,0 +1,114 @@
+"""Tests for config.py module."""
+
+import click
+import pytest
+from click.testing import CliRunner
+from pathlib import Path
+from shinkuro.config import config_callback, load_config
+from .mocks import MockLogger
+
+
+def _command() -> click.Command:
+    @click.command()
+    @click.option("--config", is_eager=True, callback=config_callback)
+    @click.option("--folder", multiple=True, envvar="FOLDER")
+    @click.option("--git-url", envvar="GIT_URL")
+    @click.option("--cache-dir", envvar="CACHE_DIR", default="~/.shinkuro/remote")
+    @click.option("--auto-pull", is_flag=True, envvar="AUTO_PULL")
+    def command(config, folder, git_url, cache_dir, auto_pull):
+        click.echo(f"{list(folder)} {git_url} {cache_dir} {auto_pull}")
+
+    return command
+
+
+def _write_config(tmp_path: Path, content: str) -> Path:
+    path = tmp_path / "shinkuro.toml"
+    path.write_text(content)
+    return path
+
+
+def test_load_config(tmp_path):
+    path = _write_config(
+        tmp_path,
+        'folder = "prompts"\ngit_url = "https://github.com/o/r.git"\nauto_pull = true\n',
+    )
+
+    assert load_config(path) == {
+        "folder": ["prompts"],
+        "git_url": "https://github.com/o/r.git",
+        "auto_pull": True,
+    }
+
+
+def test_load_config_warns_unknown_keys(tmp_path):
+    path = _write_config(tmp_path, 'folder = ["a", "b"]\nfolders = "c"\n')
+    logger = MockLogger()
+
+    assert load_config(path, logger=logger) == {"folder": ["a", "b"]}
+    assert logger.warnings == [f"unknown key 'folders' in config file {path}, ignoring"]
+
+
+def test_load_config_malformed(tmp_path):
+    path = _write_config(tmp_path, "folder = \n")
+
+    with pytest.raises(ValueError, match="Invalid TOML in config file"):
+        load_config(path)
+
+
+def test_config_precedence(tmp_path):
+    path = _write_config(
+        tmp_path,
+        'folder = "from-config"\ngit_url = "config-url"\ncache_dir = "config-cache"\n',
+    )
+    runner = CliRunner()
+
+    result = runner.invoke(_command(), ["--config", str(path)])
+    assert result.output.strip() == "['from-config'] config-url config-cache False"
+
+    result = runner.invoke(
+        _command(),
+        ["--config", str(path)],
+        env={"GIT_URL": "env-url", "CACHE_DIR": "env-cache"},
+    )
+    assert result.output.strip() == "['from-config'] env-url env-cache False"
+
+    result = runner.invoke(
+        _command(),
+        ["--config", str(path), "--git-url", "cli-url", "--folder", "cli"],
+        env={"GIT_URL": "env-url"},
+    )
+    assert result.output.strip() == "['cli'] cli-url config-cache False"
+
+
+def test_config_default_path(tmp_path, monkeypatch):
+    path = _write_config(tmp_path, "auto_pull = true\n")
+    monkeypatch.setattr("shinkuro.config.DEFAULT_CONFIG_PATH", path)
+
+    result = CliRunner().invoke(_command(), [])
+
+    assert result.output.strip() == "[] None ~/.shinkuro/remote True"
+
+
+def test_config_missing_default_path_is_ignored(tmp_path, monkeypatch):
+    monkeypatch.setattr("shinkuro.config.DEFAULT_CONFIG_PATH", tmp_path / "none.toml")
+
+    result = CliRunner().invoke(_command(), [])
+
+    assert result.exit_code == 0
+    assert result.output.strip() == "[] None ~/.shinkuro/remote False"
+
+
+def test_config_errors_are_reported(tmp_path):
+    path = _write_config(tmp_path, "[broken\n")
+
+    result = CliRunner().invoke(_command(), ["--config", str(path)])
+
+    assert result.exit_code == 2
+    assert "Invalid TOML in config file" in result.output
+
+
+def test_config_missing_explicit_path(tmp_path):
+    result = CliRunner().invoke(_command(), ["--config", str(tmp_path / "none.toml")])
+
+    assert result.exit_code == 2
+    assert "No such file" in result.output
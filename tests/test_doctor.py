@@ -0,0 +1,217 @@
+"""Tests for doctor.py module."""
+
+from pathlib import Path
+from shinkuro.doctor import Check, check_prompts, format_report, run_checks
+from shinkuro.formatters import BraceFormatter
+from .fixtures import create_prompt_data
+
+
+def _run(folder=None, git_url=None, cache_dir=Path("/cache")):
+    folders = [folder] if folder else []
+    return run_checks(folders, git_url, cache_dir, False, BraceFormatter(), False)
+
+
+def test_run_checks_missing_folder(tmp_path):
+    missing = tmp_path / "missing"
+
+    checks = _run(folder=str(missing))
+
+    assert len(checks) == 1
+    assert checks[0].name == "folder"
+    assert checks[0].passed is False
+    assert f"'{missing}' does not exist" in checks[0].message
+    report = format_report(checks)
+    assert "[FAIL] folder" in report
+    assert "hint: check the --folder path" in report
+
+
+def test_run_checks_no_configuration():
+    checks = _run()
+
+    assert checks[0].passed is False
+    assert "neither folder nor git-url" in checks[0].message
+
+
+def test_run_checks_no_markdown_files(tmp_path):
+    (tmp_path / "notes.txt").write_text("not a prompt")
+
+    checks = _run(folder=str(tmp_path))
+
+    assert checks[-1].name == "markdown files"
+    assert checks[-1].passed is False
+
+
+def test_run_checks_reports_each_file(tmp_path):
+    (tmp_path / "good.md").write_text("Hello")
+    (tmp_path / "bad.md").write_text(
+        "---\narguments:\n  - name: user\n---\nHello {name}"
+    )
+
+    checks = _run(folder=str(tmp_path))
+
+    by_name = {check.name: check for check in checks}
+    assert by_name["good.md"].passed is True
+    assert by_name["bad.md"].passed is False
+    assert "don't match provided arguments" in by_name["bad.md"].message
+
+
+def test_run_checks_skips_sync_artifacts(tmp_path):
+    (tmp_path / "review.md").write_text("Review")
+    (tmp_path / "review.sync-conflict-20240101-120000-ABCDEFG.md").write_text("{")
+
+    checks = _run(folder=str(tmp_path))
+
+    assert [check.name for check in checks] == [
+        "folder",
+        "markdown files",
+        "review.md",
+        "prompt names",
+    ]
+    assert all(check.passed for check in checks)
+
+
+def test_run_checks_skips_hidden_files(tmp_path):
+    (tmp_path / "review.md").write_text("Review")
+    (tmp_path / ".hidden").mkdir()
+    (tmp_path / ".hidden" / "x.md").write_text("Hello {name")
+
+    checks = _run(folder=str(tmp_path))
+    with_hidden = run_checks(
+        [str(tmp_path)],
+        None,
+        Path("/cache"),
+        False,
+        BraceFormatter(),
+        False,
+        include_hidden=True,
+    )
+
+    assert [check.name for check in checks][-2] == "review.md"
+    assert all(check.passed for check in checks)
+    assert "x.md" in {Path(check.name).name for check in with_hidden}
+
+
+def test_run_checks_applies_shinkuroignore(tmp_path):
+    (tmp_path / ".shinkuroignore").write_text("drafts/**\n")
+    (tmp_path / "review.md").write_text("Review")
+    (tmp_path / "drafts").mkdir()
+    (tmp_path / "drafts" / "wip.md").write_text("Hello {name")
+
+    checks = _run(folder=str(tmp_path))
+
+    assert [check.name for check in checks] == [
+        "folder",
+        "markdown files",
+        "review.md",
+        "prompt names",
+    ]
+    assert all(check.passed for check in checks)
+
+
+def test_run_checks_applies_include(tmp_path):
+    (tmp_path / "prompts").mkdir()
+    (tmp_path / "prompts" / "review.md").write_text("Review")
+    (tmp_path / "README.md").write_text("See {docs")
+
+    checks = run_checks(
+        [str(tmp_path)],
+        None,
+        Path("/cache"),
+        False,
+        BraceFormatter(),
+        False,
+        include=["prompts/**"],
+    )
+
+    assert checks[-2].name == str(Path("prompts") / "review.md")
+    assert all(check.passed for check in checks)
+
+
+def test_run_checks_uses_namespace_separator(tmp_path):
+    (tmp_path / "coding").mkdir()
+    (tmp_path / "coding" / "review.md").write_text("Review")
+
+    checks = run_checks(
+        [str(tmp_path)],
+        None,
+        Path("/cache"),
+        False,
+        BraceFormatter(),
+        False,
+        namespace_separator="/",
+    )
+
+    by_name = {check.name: check for check in checks}
+    assert by_name[str(Path("coding") / "review.md")].message == (
+        "loads as prompt 'coding/review'"
+    )
+
+
+def test_run_checks_reports_duplicate_names(tmp_path):
+    (tmp_path / "a.md").write_text("---\nname: review\n---\nFirst")
+    (tmp_path / "b.md").write_text("---\nname: review\n---\nSecond")
+
+    checks = _run(folder=str(tmp_path))
+
+    assert checks[-1].name == "prompt names"
+    assert checks[-1].passed is False
+    assert "duplicate prompt name 'review'" in checks[-1].message
+    assert "a.md" in checks[-1].message and "b.md" in checks[-1].message
+
+
+def test_run_checks_git_checks_once_for_several_folders(tmp_path):
+    git_url = "https://github.com/user/repo.git"
+    repo_path = tmp_path / "git" / "user" / "repo"
+    for folder in ["team", "personal"]:
+        (repo_path / folder).mkdir(parents=True)
+        (repo_path / folder / f"{folder}.md").write_text("Hello")
+
+    checks = run_checks(
+        ["team", "personal"], git_url, tmp_path, False, BraceFormatter(), False
+    )
+
+    names = [check.name for check in checks]
+    assert names.count("git url") == 1
+    assert names.count("cache dir") == 1
+    assert names.count("folder") == 2
+    assert names[-1] == "prompt names"
+    assert all(check.passed for check in checks)
+
+
+def test_run_checks_invalid_git_url():
+    checks = _run(git_url="invalid-url")
+
+    assert checks[0].name == "git url"
+    assert checks[0].passed is False
+
+
+def test_run_checks_git_not_cloned(tmp_path):
+    checks = _run(git_url="https://github.com/user/repo.git", cache_dir=tmp_path)
+
+    assert [check.name for check in checks] == ["git url", "cache dir", "repository"]
+    assert all(check.passed for check in checks)
+
+
+def test_format_report_all_passed():
+    report = format_report([Check("folder", True, "ok")])
+
+    assert report == "[PASS] folder: ok\nall 1 checks passed"
+
+
+def test_check_prompts_reports_failures_and_build_errors():
+    good = create_prompt_data(name="good", path=Path("/p/good.md"))
+    bad = create_prompt_data(name="bad", path=Path("/p/bad.md"))
+
+    def build(prompt_data):
+        if prompt_data.name == "bad":
+            raise ValueError("Content arguments differ")
+        return prompt_data
+
+    checks = check_prompts([good, bad], [(Path("/p/broken.md"), "invalid YAML")], build)
+
+    assert [(c.name, c.passed) for c in checks] == [
+        ("/p/broken.md", False),
+        ("/p/good.md", True),
+        ("/p/bad.md", False),
+    ]
+    assert checks[2].message == "Content arguments differ"
@@ -0,0 +1,25 @@
+"""Tests for envvars.py module."""
+
+import os
+
+from shinkuro.envvars import ENV_VAR_FOLDERS, ENV_VAR_LINES, split_folders
+
+
+def test_env_var_lines_keeps_spaces():
+    assert ENV_VAR_LINES.split_envvar_value("api key: \\S+") == ["api key: \\S+"]
+
+
+def test_env_var_lines_splits_lines():
+    assert ENV_VAR_LINES.split_envvar_value("a b\n\nc\n") == ["a b", "c"]
+
+
+def test_split_folders():
+    assert split_folders("team,personal\nproject") == ["team", "personal", "project"]
+    assert split_folders(f"team{os.pathsep}personal , ") == ["team", "personal"]
+
+
+def test_env_var_folders_splits_folder_lists():
+    assert ENV_VAR_FOLDERS.split_envvar_value("My Team,personal") == [
+        "My Team",
+        "personal",
+    ]
@@ -0,0 +1,87 @@
+"""Tests for errors.py module."""
+
+import pytest
+from fastmcp import Client, FastMCP
+from mcp.shared.exceptions import McpError
+from mcp.types import INVALID_PARAMS
+from shinkuro.errors import MissingRequired, PromptError, PromptErrorMiddleware
+from shinkuro.formatters import BraceFormatter
+from shinkuro.prompts.markdown import MarkdownPrompt
+from .fixtures import create_argument, create_prompt_data
+
+
+def _server(**prompt_options) -> FastMCP:
+    mcp = FastMCP(name="test")
+    prompt_data = create_prompt_data(
+        name="greet",
+        arguments=[create_argument("user", "User name", None)],
+        content="Hello {user}",
+    )
+    mcp.add_prompt(
+        MarkdownPrompt.from_prompt_data(prompt_data, BraceFormatter(), **prompt_options)
+    )
+    mcp.add_middleware(PromptErrorMiddleware())
+    return mcp
+
+
+def test_prompt_error_is_value_error():
+    assert issubclass(PromptError, ValueError)
+
+
+def test_prompt_error_to_mcp_error():
+    error = MissingRequired({"user"})
+
+    mcp_error = error.to_mcp_error()
+
+    assert mcp_error.error.code == INVALID_PARAMS
+    assert mcp_error.error.message == "Missing required arguments: {'user'}"
+
+
+@pytest.mark.asyncio
+async def test_prompt_error_middleware_sends_invalid_params():
+    async with Client(_server()) as client:
+        with pytest.raises(McpError) as exc_info:
+            await client.get_prompt("greet", {})
+
+    assert exc_info.value.error.code == INVALID_PARAMS
+    assert exc_info.value.error.message == "Missing required arguments: {'user'}"
+
+
+@pytest.mark.asyncio
+async def test_prompt_error_middleware_passes_results():
+    async with Client(_server()) as client:
+        result = await client.get_prompt("greet", {"user": "Ann"})
+
+    assert result.messages[0].content.text == "Hello Ann"
+
+
+@pytest.mark.asyncio
+async def test_prompt_error_middleware_arguments_too_large():
+    async with Client(_server(max_argument_bytes=8)) as client:
+        with pytest.raises(McpError) as exc_info:
+            await client.get_prompt("greet", {"user": "x" * 9})
+
+    assert exc_info.value.error.code == INVALID_PARAMS
+    assert "exceeds the limit of 8 bytes" in exc_info.value.error.message
+
+
+@pytest.mark.asyncio
+async def test_prompt_error_middleware_unexpected_arguments():
+    async with Client(_server()) as client:
+        with pytest.raises(McpError) as exc_info:
+            await client.get_prompt("greet", {"user": "Ann", "usr": "Bob"})
+
+    assert exc_info.value.error.code == INVALID_PARAMS
+    assert exc_info.value.error.message == "Unexpected arguments: {'usr'}"
+
+
+@pytest.mark.asyncio
+async def test_prompt_error_middleware_unknown_prompt():
+    async with Client(_server()) as client:
+        with pytest.raises(McpError) as exc_info:
+            await client.get_prompt("missing", {})
+        result = await client.get_prompt("greet", {"user": "Ann"})
+
+    assert exc_info.value.error.code == INVALID_PARAMS
+    assert exc_info.value.error.message == "Unknown prompt: missing"
+    assert result.messages[0].content.text == "Hello Ann"
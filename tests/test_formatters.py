@@ -4,10 +4,14 @@ import pytest
 from shinkuro.formatters import (
     BraceFormatter,
     DollarFormatter,
+    HandlebarsFormatter,
     get_formatter,
+    resolve_formatter,
     validate_variable_name,
 )
+from shinkuro.errors import InvalidArgumentType
 from shinkuro.model import FormatterType
+from .fixtures import create_prompt_data
 
 
 def test_validate_variable_name_valid():
@@ -42,6 +46,140 @@ def test_brace_formatter_format():
     assert result == "Hello Alice!"
 
 
+def test_brace_formatter_inline_default():
+    formatter = BraceFormatter()
+    content = "Hello {name:World}!"
+    assert formatter.extract_arguments(content) == {"name"}
+    assert formatter.extract_defaults(content) == {"name": "World"}
+    assert formatter.format(content, {}) == "Hello World!"
+    assert formatter.format(content, {"name": "Alice"}) == "Hello Alice!"
+
+
+def test_brace_formatter_inline_default_with_colons():
+    formatter = BraceFormatter()
+    content = "Open {url:http://localhost:8080}"
+    assert formatter.extract_defaults(content) == {"url": "http://localhost:8080"}
+    assert formatter.format(content, {}) == "Open http://localhost:8080"
+
+
+def test_brace_formatter_repeated_inline_default():
+    formatter = BraceFormatter()
+    content = "{lang:rust} and {lang:rust} or {lang}"
+    assert formatter.extract_defaults(content) == {"lang": "rust"}
+
+
+def test_brace_formatter_conflicting_inline_defaults():
+    with pytest.raises(ValueError, match="Conflicting inline defaults for 'lang'"):
+        BraceFormatter().extract_defaults("{lang:rust} and {lang:go}")
+
+
+def test_brace_formatter_format_spec_needs_conversion():
+    formatter = BraceFormatter()
+    content = "Total: [{amount!s:>10}]"
+    assert formatter.extract_arguments(content) == {"amount"}
+    assert formatter.extract_defaults(content) == {}
+    assert formatter.format(content, {"amount": "42"}) == "Total: [        42]"
+    with pytest.raises(KeyError):
+        formatter.format(content, {})
+
+
+def test_brace_formatter_spec_like_defaults_stay_defaults():
+    formatter = BraceFormatter()
+    content = "{retries:3} {unit:s} {sep:,} {mode:x}"
+    assert formatter.extract_defaults(content) == {
+        "retries": "3",
+        "unit": "s",
+        "sep": ",",
+        "mode": "x",
+    }
+    assert formatter.format(content, {}) == "3 s , x"
+
+
+def test_brace_formatter_different_format_specs_do_not_conflict():
+    formatter = BraceFormatter()
+    content = "[{x!s:>10}] [{x!s:<10}]"
+    assert formatter.extract_defaults(content) == {}
+    assert formatter.format(content, {"x": "ab"}) == "[        ab] [ab        ]"
+
+
+def test_brace_formatter_numeric_format_spec_on_text_value():
+    formatter = BraceFormatter()
+    assert formatter.format("{price!s:.2f}", {"price": "3.14159"}) == "3.14"
+    assert formatter.format("{count!s:03d}", {"count": "7"}) == "007"
+
+
+def test_brace_formatter_numeric_format_spec_rejects_text():
+    with pytest.raises(InvalidArgumentType, match="'amount' must be a number"):
+        BraceFormatter().format("{amount!s:.2f}", {"amount": "abc"})
+
+
+def test_brace_formatter_without_default():
+    formatter = BraceFormatter()
+    assert formatter.extract_defaults("Hello {user}") == {}
+    with pytest.raises(KeyError):
+        formatter.format("Hello {user}", {})
+
+
+def test_brace_formatter_format_escaped_braces():
+    formatter = BraceFormatter()
+    result = formatter.format("{{literal}} {user}", {"user": "Alice"})
+    assert result == "{literal} Alice"
+
+
+def test_brace_formatter_backslash_escaped_braces():
+    formatter = BraceFormatter()
+    content = r"Use \{braces\} literally, {user}"
+    assert formatter.extract_arguments(content) == {"user"}
+    result = formatter.format(content, {"user": "Alice"})
+    assert result == "Use {braces} literally, Alice"
+
+
+def test_brace_formatter_conditional_section():
+    formatter = BraceFormatter()
+    content = "Review this.{#if context} Context: {context}{/if}"
+    assert formatter.extract_arguments(content) == {"context"}
+    assert formatter.extract_defaults(content) == {"context": ""}
+    assert formatter.format(content, {"context": "CI"}) == "Review this. Context: CI"
+    assert formatter.format(content, {"context": ""}) == "Review this."
+    assert formatter.format(content, {}) == "Review this."
+
+
+def test_brace_formatter_conditional_keeps_unconditional_required():
+    formatter = BraceFormatter()
+    content = "{user}{#if note} ({note}, {user}){/if}"
+    assert formatter.extract_arguments(content) == {"user", "note"}
+    assert formatter.extract_defaults(content) == {"note": ""}
+
+
+def test_brace_formatter_mixed_escaped_and_unescaped():
+    formatter = BraceFormatter()
+    content = 'Send {{"user": "{user}", "id": {{id}}}} as {format}'
+    assert formatter.extract_arguments(content) == {"user", "format"}
+    result = formatter.format(content, {"user": "Alice", "format": "JSON"})
+    assert result == 'Send {"user": "Alice", "id": {id}} as JSON'
+
+
+def test_brace_formatter_escaped_conditional_is_literal():
+    formatter = BraceFormatter()
+    content = "{{#if x}}{user}"
+    assert formatter.extract_arguments(content) == {"user"}
+    assert formatter.format(content, {"user": "Alice"}) == "{#if x}Alice"
+
+
+@pytest.mark.parametrize(
+    "content, message",
+    [
+        ("{#if a}{#if b}x{/if}{/if}", "nested"),
+        ("{#if a}x", "unterminated"),
+        ("x{/if}", "without a matching"),
+        ("{#if a-b}x{/if}", "Invalid variable name"),
+    ],
+)
+def test_brace_formatter_invalid_conditional(content, message):
+    with pytest.raises(ValueError, match=message):
+        BraceFormatter().extract_arguments(content)
+
+
 def test_dollar_formatter_extract_arguments():
     formatter = DollarFormatter()
     arguments = formatter.extract_arguments("Hello $user from $project")
@@ -60,6 +198,54 @@ def test_dollar_formatter_safe_substitute():
     assert result == "Hello Alice $missing"
 
 
+def test_dollar_formatter_mixed_escaped_and_unescaped():
+    formatter = DollarFormatter()
+    content = "Price $$amount for $user, escaped $${user}"
+    assert formatter.extract_arguments(content) == {"user"}
+    result = formatter.format(content, {"user": "Alice"})
+    assert result == "Price $amount for Alice, escaped ${user}"
+
+
+def test_dollar_formatter_backslash_escaped_dollar():
+    formatter = DollarFormatter()
+    content = r"Costs \$amount for $user"
+    assert formatter.extract_arguments(content) == {"user"}
+    result = formatter.format(content, {"user": "Alice", "amount": "5"})
+    assert result == "Costs $amount for Alice"
+
+
+def test_handlebars_formatter_extract_arguments():
+    formatter = HandlebarsFormatter()
+    arguments = formatter.extract_arguments("Hello {{user}} from {{ project }}")
+    assert arguments == {"user", "project"}
+
+
+def test_handlebars_formatter_extract_arguments_ignores_single_braces():
+    formatter = HandlebarsFormatter()
+    arguments = formatter.extract_arguments('Hello {{user}}, data: {"key": {x}}')
+    assert arguments == {"user"}
+
+
+def test_handlebars_formatter_extract_arguments_invalid():
+    formatter = HandlebarsFormatter()
+    with pytest.raises(ValueError, match="Invalid variable name"):
+        formatter.extract_arguments("Hello {{123}}")
+
+
+def test_handlebars_formatter_format():
+    formatter = HandlebarsFormatter()
+    result = formatter.format(
+        "Hello {{user}} and {{ friend }}!", {"user": "Alice", "friend": "Bob"}
+    )
+    assert result == "Hello Alice and Bob!"
+
+
+def test_handlebars_formatter_leaves_unmatched_and_single_braces():
+    formatter = HandlebarsFormatter()
+    result = formatter.format("{{user}} {{missing}} {user} $user", {"user": "Alice"})
+    assert result == "Alice {{missing}} {user} $user"
+
+
 def test_get_formatter_brace():
     formatter = get_formatter(FormatterType.BRACE)
     assert isinstance(formatter, BraceFormatter)
@@ -70,6 +256,20 @@ def test_get_formatter_dollar():
     assert isinstance(formatter, DollarFormatter)
 
 
+def test_get_formatter_handlebars():
+    formatter = get_formatter(FormatterType.HANDLEBARS)
+    assert isinstance(formatter, HandlebarsFormatter)
+
+
 def test_get_formatter_invalid():
     with pytest.raises(ValueError, match="Unknown formatter"):
         get_formatter("invalid")  # type: ignore
+
+
+def test_resolve_formatter_uses_prompt_variable_format():
+    default = BraceFormatter()
+    prompt_data = create_prompt_data()
+    assert resolve_formatter(prompt_data, default) is default
+
+    prompt_data.variable_format = FormatterType.DOLLAR
+    assert isinstance(resolve_formatter(prompt_data, default), DollarFormatter)
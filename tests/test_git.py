@@ -1,9 +1,24 @@
 """Tests for remote/git.py module."""
 
+import os
+import shutil
+import subprocess
+import threading
+import time
 import pytest
 from pathlib import Path
-from shinkuro.remote.git import get_local_cache_path, clone_or_update_repo
-from .mocks import MockGit
+from typing import Optional
+from shinkuro.remote.git import (
+    get_local_cache_path,
+    parse_git_url,
+    clone_or_update_repo,
+    find_lfs_pointers,
+    fetch_lfs_objects,
+    _is_safe_path_component,
+)
+from shinkuro.interfaces import DefaultGit
+from shinkuro.remote.lock import file_lock
+from .mocks import MockGit, MockLogger
 
 
 def test_get_local_cache_path_github():
@@ -69,6 +84,33 @@ def test_get_local_cache_path_gitlab_with_credentials():
     assert result == Path("/cache/git/owner/repo")
 
 
+@pytest.mark.parametrize(
+    "git_url, expected",
+    [
+        (
+            "https://gitlab.com/group/subgroup/repo.git",
+            "/cache/git/group+subgroup/repo",
+        ),
+        ("git@gitlab.com:group/subgroup/repo.git", "/cache/git/group+subgroup/repo"),
+        ("ssh://git@host:2222/group/repo.git", "/cache/git/group/repo"),
+        ("file:///srv/git/team/prompts.git", "/cache/git/srv+git+team/prompts"),
+        ("https://github.com/owner/repo/", "/cache/git/owner/repo"),
+    ],
+)
+def test_get_local_cache_path_url_forms(git_url, expected):
+    assert get_local_cache_path(git_url, Path("/cache")) == Path(expected)
+
+
+def test_get_local_cache_path_nested_owner_does_not_collide():
+    cache_dir = Path("/cache")
+    parent = get_local_cache_path("https://gitlab.com/group/sub.git", cache_dir)
+    nested = get_local_cache_path("https://gitlab.com/group/sub/repo.git", cache_dir)
+
+    assert parent == Path("/cache/git/group/sub")
+    assert nested == Path("/cache/git/group+sub/repo")
+    assert parent not in nested.parents
+
+
 def test_get_local_cache_path_invalid_url():
     cache_dir = Path("/cache")
     git_url = "invalid-url"
@@ -77,6 +119,66 @@ def test_get_local_cache_path_invalid_url():
         get_local_cache_path(git_url, cache_dir)
 
 
+@pytest.mark.parametrize(
+    "git_url",
+    [
+        "git@h:../../etc/x.git",
+        "git@h:owner/...git",
+        "https://github.com/../repo.git",
+        "https://github.com/owner/..",
+        "https://github.com/ow ner/repo.git",
+    ],
+)
+def test_get_local_cache_path_rejects_traversal(git_url):
+    with pytest.raises(ValueError):
+        get_local_cache_path(git_url, Path("/cache"))
+
+
+@pytest.mark.parametrize(
+    "git_url, expected",
+    [
+        ("https://github.com/owner/repo.git", (["owner"], "repo")),
+        ("git@gitlab.com:group/subgroup/repo.git", (["group", "subgroup"], "repo")),
+        ("ssh://git@host:2222/group/repo", (["group"], "repo")),
+    ],
+)
+def test_parse_git_url(git_url, expected):
+    assert parse_git_url(git_url) == expected
+
+
+@pytest.mark.parametrize(
+    "git_url",
+    [
+        "git@h:../../etc/x.git",
+        "git@h:owner/...git",
+        "https://github.com/../repo.git",
+        "https://github.com/owner/..",
+        "https://github.com/ow ner/repo.git",
+    ],
+)
+def test_parse_git_url_rejects_traversal(git_url):
+    with pytest.raises(ValueError, match="Unsafe path component"):
+        parse_git_url(git_url)
+
+
+@pytest.mark.parametrize(
+    "component, safe",
+    [
+        ("repo", True),
+        ("my.repo-name_2", True),
+        ("~user", True),
+        ("..", False),
+        (".", False),
+        ("a/b", False),
+        ("a\\b", False),
+        ("/etc", False),
+        ("", False),
+    ],
+)
+def test_is_safe_path_component(component, safe):
+    assert _is_safe_path_component(component) is safe
+
+
 def test_clone_or_update_repo_clone_new(tmp_path):
     git = MockGit()
     git_url = "https://github.com/user/repo.git"
@@ -113,3 +215,220 @@ def test_clone_or_update_repo_exists_with_pull(tmp_path):
     assert len(git.cloned) == 0
     assert len(git.pulled) == 1
     assert git.pulled[0] == local_path
+
+
+LFS_POINTER = (
+    "version https://git-lfs.github.com/spec/v1\n"
+    "oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n"
+    "size 12345\n"
+)
+
+
+def test_find_lfs_pointers(tmp_path):
+    (tmp_path / "real.md").write_text("# Real content")
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "big.md").write_text(LFS_POINTER)
+    (tmp_path / ".git").mkdir()
+    (tmp_path / ".git" / "ignored").write_text(LFS_POINTER)
+
+    assert list(find_lfs_pointers(tmp_path)) == [tmp_path / "docs" / "big.md"]
+
+
+def test_fetch_lfs_objects_pulls_when_installed(tmp_path):
+    git = MockGit()
+    logger = MockLogger()
+
+    fetch_lfs_objects(tmp_path, git=git, logger=logger)
+
+    assert git.lfs_pulled == [tmp_path]
+    assert logger.warnings == []
+
+
+def test_fetch_lfs_objects_warns_without_git_lfs(tmp_path):
+    git = MockGit(lfs_installed=False)
+    logger = MockLogger()
+    (tmp_path / "big.md").write_text(LFS_POINTER)
+
+    fetch_lfs_objects(tmp_path, git=git, logger=logger)
+
+    assert git.lfs_pulled == []
+    assert len(logger.warnings) == 1
+    assert "git-lfs is not installed" in logger.warnings[0]
+    assert "big.md" in logger.warnings[0]
+
+
+def test_fetch_lfs_objects_no_pointers_no_warning(tmp_path):
+    git = MockGit(lfs_installed=False)
+    logger = MockLogger()
+    (tmp_path / "real.md").write_text("# Real content")
+
+    fetch_lfs_objects(tmp_path, git=git, logger=logger)
+
+    assert logger.warnings == []
+
+
+class SlowMockGit(MockGit):
+    """Mock git whose clone takes a while and creates the directory."""
+
+    def clone(self, url: str, path: Path, ref: Optional[str] = None) -> None:
+        time.sleep(0.2)
+        path.mkdir()
+        super().clone(url, path, ref)
+
+
+def test_clone_or_update_repo_concurrent_clones_serialized(tmp_path):
+    git = SlowMockGit()
+    git_url = "https://github.com/user/repo.git"
+    local_path = tmp_path / "repo"
+    errors = []
+
+    def update():
+        try:
+            clone_or_update_repo(git_url, local_path, False, git=git)
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=update) for _ in range(2)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert errors == []
+    assert git.cloned == [{"url": git_url, "path": local_path, "ref": None}]
+
+
+def test_clone_or_update_repo_lock_timeout(tmp_path):
+    git = MockGit()
+    local_path = tmp_path / "repo"
+
+    with file_lock(tmp_path / "repo.lock"):
+        with pytest.raises(TimeoutError, match="Timed out"):
+            clone_or_update_repo(
+                "https://github.com/user/repo.git",
+                local_path,
+                False,
+                git=git,
+                lock_timeout=0.2,
+            )
+
+    assert git.cloned == []
+
+
+def test_get_local_cache_path_with_ref():
+    cache_dir = Path("/cache")
+
+    assert (
+        get_local_cache_path("https://github.com/user/repo.git", cache_dir, "v1.2.0")
+        == cache_dir / "git" / "user" / "repo@v1.2.0"
+    )
+    assert (
+        get_local_cache_path("https://github.com/user/repo.git", cache_dir, "feat/x")
+        == cache_dir / "git" / "user" / "repo@feat%2Fx"
+    )
+
+
+@pytest.mark.parametrize(
+    "first, second",
+    [("feature/x", "feature_x"), ("release/1.0", "release_1.0"), ("a%2Fb", "a/b")],
+)
+def test_get_local_cache_path_distinct_refs_do_not_collide(first, second):
+    git_url = "https://github.com/user/repo.git"
+    cache_dir = Path("/cache")
+
+    assert get_local_cache_path(git_url, cache_dir, first) != get_local_cache_path(
+        git_url, cache_dir, second
+    )
+
+
+def test_clone_or_update_repo_clone_with_ref(tmp_path):
+    git = MockGit()
+    git_url = "https://github.com/user/repo.git"
+    local_path = tmp_path / "repo"
+
+    clone_or_update_repo(git_url, local_path, False, ref="v1.2.0", git=git)
+
+    assert git.cloned == [{"url": git_url, "path": local_path, "ref": "v1.2.0"}]
+
+
+def test_clone_or_update_repo_pull_branch_ref(tmp_path):
+    git = MockGit()
+    local_path = tmp_path / "repo"
+    local_path.mkdir()
+
+    clone_or_update_repo(
+        "https://github.com/user/repo.git", local_path, True, ref="release", git=git
+    )
+
+    assert git.pulled == [local_path]
+
+
+def test_clone_or_update_repo_pinned_ref_not_pulled(tmp_path):
+    git = MockGit(detached=True)
+    local_path = tmp_path / "repo"
+    local_path.mkdir()
+
+    logger = MockLogger()
+
+    clone_or_update_repo(
+        "https://github.com/user/repo.git",
+        local_path,
+        True,
+        ref="v1.2.0",
+        git=git,
+        logger=logger,
+    )
+
+    assert git.pulled == []
+    assert logger.debugs == [f"not pulling {local_path}, 'v1.2.0' is not a branch"]
+
+
+def _git(cwd: Path, *args: str) -> None:
+    identity = {
+        "GIT_AUTHOR_NAME": "test",
+        "GIT_AUTHOR_EMAIL": "test@example.com",
+        "GIT_COMMITTER_NAME": "test",
+        "GIT_COMMITTER_EMAIL": "test@example.com",
+    }
+    subprocess.run(["git", *args], cwd=cwd, env={**os.environ, **identity}, check=True)
+
+
+def _commit(repo: Path, content: str) -> None:
+    (repo / "hello.md").write_text(content)
+    _git(repo, "add", "hello.md")
+    _git(repo, "commit", "-q", "-m", content)
+
+
+@pytest.mark.skipif(shutil.which("git") is None, reason="git is not installed")
+def test_clone_or_update_repo_fixture_repo_refs(tmp_path):
+    origin = tmp_path / "origin"
+    origin.mkdir()
+    _git(origin, "init", "-q")
+    _git(origin, "checkout", "-q", "-b", "main")
+    _commit(origin, "main")
+    _git(origin, "checkout", "-q", "-b", "prompts")
+    _commit(origin, "prompts v1")
+    _git(origin, "tag", "v1")
+    _git(origin, "checkout", "-q", "main")
+    git_url = f"file://{origin}"
+    git = DefaultGit()
+
+    default = tmp_path / "default"
+    clone_or_update_repo(git_url, default, False, git=git)
+    assert (default / "hello.md").read_text() == "main"
+
+    branch = tmp_path / "branch"
+    clone_or_update_repo(git_url, branch, False, ref="prompts", git=git)
+    assert (branch / "hello.md").read_text() == "prompts v1"
+    assert git.on_branch(branch)
+
+    tag = tmp_path / "tag"
+    clone_or_update_repo(git_url, tag, False, ref="v1", git=git)
+    assert not git.on_branch(tag)
+
+    _git(origin, "checkout", "-q", "prompts")
+    _commit(origin, "prompts v2")
+    clone_or_update_repo(git_url, branch, True, ref="prompts", git=git)
+    clone_or_update_repo(git_url, tag, True, ref="v1", git=git)
+    assert (branch / "hello.md").read_text() == "prompts v2"
+    assert (tag / "hello.md").read_text() == "prompts v1"
@@ -1,8 +1,16 @@
 """Tests for interfaces.py module."""
 
+import base64
+import json
+import os
 import sys
+import pytest
+from contextlib import nullcontext
 from io import StringIO
+from pathlib import Path
+from git import GitCommandError
 from shinkuro.interfaces import DefaultFileSystem, DefaultLogger, DefaultGit
+from shinkuro.model import LogFormat, LogLevel
 
 
 def test_default_filesystem_read_text(tmp_path):
@@ -52,6 +60,16 @@ def test_default_filesystem_is_dir(tmp_path):
     assert fs.is_dir(test_file) is False
 
 
+def test_default_filesystem_mtime(tmp_path):
+    test_file = tmp_path / "file.md"
+    test_file.write_text("content")
+    os.utime(test_file, ns=(1_000_000_000, 2_000_000_000))
+
+    fs = DefaultFileSystem()
+
+    assert fs.mtime(test_file) == 2_000_000_000
+
+
 def test_default_logger_warning():
     logger = DefaultLogger()
     captured = StringIO()
@@ -66,6 +84,39 @@ def test_default_logger_warning():
     assert "Warning: test warning message" in output
 
 
+@pytest.mark.parametrize(
+    "level, expected",
+    [
+        (LogLevel.OFF, []),
+        (LogLevel.ERROR, ["Error: e"]),
+        (LogLevel.WARN, ["Error: e", "Warning: w"]),
+        (LogLevel.INFO, ["Error: e", "Warning: w", "Info: i"]),
+        (LogLevel.DEBUG, ["Error: e", "Warning: w", "Info: i", "Debug: d"]),
+    ],
+)
+def test_default_logger_level(level, expected, monkeypatch, capsys):
+    monkeypatch.setattr(DefaultLogger, "level", level)
+    logger = DefaultLogger()
+
+    logger.error("e")
+    logger.warning("w")
+    logger.info("i")
+    logger.debug("d")
+
+    captured = capsys.readouterr()
+    assert captured.err.splitlines() == expected
+    assert captured.out == ""
+
+
+def test_default_logger_json_format(monkeypatch, capsys):
+    monkeypatch.setattr(DefaultLogger, "format", LogFormat.JSON)
+
+    DefaultLogger().warning('bad "name" field')
+
+    line = capsys.readouterr().err.strip()
+    assert json.loads(line) == {"level": "warn", "message": 'bad "name" field'}
+
+
 def test_default_git_clone(tmp_path, monkeypatch):
     clone_called = []
 
@@ -108,3 +159,123 @@ def test_default_git_pull(tmp_path, monkeypatch):
     git.pull(tmp_path)
 
     assert len(pull_called) == 1
+
+
+def test_default_git_clone_with_credentials(tmp_path, monkeypatch):
+    clone_called = []
+
+    class MockRepo:
+        @classmethod
+        def clone_from(cls, url, path, depth, env):
+            clone_called.append(env)
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    git = DefaultGit(token="secret", ssh_key="/keys/id ed25519")
+    git.clone("https://github.com/user/repo.git", tmp_path / "repo")
+
+    env = clone_called[0]
+    expected = base64.b64encode(b"x-access-token:secret").decode()
+    assert env["GIT_CONFIG_KEY_0"] == "http.extraHeader"
+    assert env["GIT_CONFIG_VALUE_0"] == f"Authorization: Basic {expected}"
+    assert env["GIT_SSH_COMMAND"] == "ssh -i '/keys/id ed25519' -o IdentitiesOnly=yes"
+    assert env["GIT_TERMINAL_PROMPT"] == "0"
+
+
+def test_default_git_clone_missing_credentials(tmp_path, monkeypatch):
+    class MockRepo:
+        @classmethod
+        def clone_from(cls, url, path, depth):
+            raise GitCommandError(
+                "git clone",
+                128,
+                "fatal: could not read Username for 'https://github.com': "
+                "terminal prompts disabled",
+            )
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    with pytest.raises(ValueError, match="Git authentication failed.*--git-token"):
+        DefaultGit().clone("https://github.com/user/private.git", tmp_path / "repo")
+
+
+def test_default_git_clone_with_username_and_passphrase(tmp_path, monkeypatch):
+    clone_called = []
+
+    class MockRepo:
+        @classmethod
+        def clone_from(cls, url, path, depth, env):
+            clone_called.append(env)
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    git = DefaultGit(token="secret", username="oauth2", ssh_key_passphrase="hunter2")
+    git.clone("https://gitlab.com/user/repo.git", tmp_path / "repo")
+
+    env = clone_called[0]
+    expected = base64.b64encode(b"oauth2:secret").decode()
+    assert env["GIT_CONFIG_VALUE_0"] == f"Authorization: Basic {expected}"
+    assert env["SSH_ASKPASS_REQUIRE"] == "force"
+    assert env["SHINKURO_SSH_KEY_PASSPHRASE"] == "hunter2"
+    askpass = Path(env["SSH_ASKPASS"])
+    assert os.access(askpass, os.X_OK)
+    # The passphrase is read from the environment, never written to the script
+    assert "hunter2" not in askpass.read_text()
+
+
+def test_default_git_clone_ssh_auth_error_names_methods(tmp_path, monkeypatch):
+    class MockRepo:
+        @classmethod
+        def clone_from(cls, url, path, depth, **kwargs):
+            raise GitCommandError(
+                "git clone", 128, "git@github.com: Permission denied (publickey)."
+            )
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    with pytest.raises(ValueError, match="tried ssh-agent, default SSH keys;"):
+        DefaultGit().clone("git@github.com:org/private.git", tmp_path / "repo")
+    with pytest.raises(ValueError, match="tried SSH key /keys/id with passphrase;"):
+        DefaultGit(ssh_key="/keys/id", ssh_key_passphrase="pw").clone(
+            "git@github.com:org/private.git", tmp_path / "repo"
+        )
+
+
+def test_default_git_pull_https_auth_error_names_methods(tmp_path, monkeypatch):
+    class MockRemote:
+        url = "https://github.com/user/private.git"
+
+        def pull(self):
+            raise GitCommandError("git pull", 128, "fatal: Authentication failed")
+
+    class MockRemotes:
+        origin = MockRemote()
+
+    class MockGit:
+        def custom_environment(self, **env):
+            return nullcontext()
+
+    class MockRepo:
+        def __init__(self, path):
+            self.remotes = MockRemotes()
+            self.git = MockGit()
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    with pytest.raises(
+        ValueError,
+        match="tried token for user 'x-access-token', git credential helpers;",
+    ):
+        DefaultGit(token="expired").pull(tmp_path)
+
+
+def test_default_git_clone_other_errors_are_kept(tmp_path, monkeypatch):
+    class MockRepo:
+        @classmethod
+        def clone_from(cls, url, path, depth):
+            raise GitCommandError("git clone", 128, "fatal: unable to access")
+
+    monkeypatch.setattr("shinkuro.interfaces.Repo", MockRepo)
+
+    with pytest.raises(GitCommandError):
+        DefaultGit().clone("https://github.com/user/repo.git", tmp_path / "repo")
@@ -2,7 +2,14 @@
 
 import pytest
 from pathlib import Path
-from shinkuro.loader import get_folder_path
+from shinkuro.loader import (
+    ContentFilter,
+    apply_overrides,
+    get_folder_path,
+    get_folder_paths,
+    parse_overrides,
+)
+from .fixtures import create_prompt_data
 
 
 def test_get_folder_path_local_folder():
@@ -29,7 +36,7 @@ def test_get_folder_path_git_only(tmp_path, monkeypatch):
     # Mock git operations
     cloned = []
 
-    def mock_clone(url, path, auto_pull, git=None):
+    def mock_clone(url, path, auto_pull, ref=None, git=None):
         cloned.append(path)
         path.mkdir(parents=True, exist_ok=True)
 
@@ -48,7 +55,7 @@ def test_get_folder_path_git_only(tmp_path, monkeypatch):
 
 def test_get_folder_path_git_with_subfolder(tmp_path, monkeypatch):
     # Mock git operations
-    def mock_clone(url, path, auto_pull, git=None):
+    def mock_clone(url, path, auto_pull, ref=None, git=None):
         path.mkdir(parents=True, exist_ok=True)
 
     monkeypatch.setattr("shinkuro.loader.clone_or_update_repo", mock_clone)
@@ -67,7 +74,7 @@ def test_get_folder_path_git_with_auto_pull(tmp_path, monkeypatch):
     # Mock git operations
     pulled = []
 
-    def mock_clone(url, path, auto_pull, git=None):
+    def mock_clone(url, path, auto_pull, ref=None, git=None):
         path.mkdir(parents=True, exist_ok=True)
         if auto_pull:
             pulled.append(path)
@@ -83,3 +90,149 @@ def test_get_folder_path_git_with_auto_pull(tmp_path, monkeypatch):
 
     assert result == tmp_path / "git" / "user" / "repo"
     assert len(pulled) == 1
+
+
+def test_content_filter_no_patterns():
+    content_filter = ContentFilter()
+    assert content_filter.matches(create_prompt_data(content="anything"))
+
+
+def test_content_filter_allow():
+    content_filter = ContentFilter(allow=[r"@public\b"])
+    assert content_filter.matches(create_prompt_data(content="Say hi @public"))
+    assert not content_filter.matches(create_prompt_data(content="Internal notes"))
+
+
+def test_content_filter_deny():
+    content_filter = ContentFilter(deny=["@internal"])
+    assert content_filter.matches(create_prompt_data(content="Say hi"))
+    assert not content_filter.matches(create_prompt_data(content="Hi @internal"))
+
+
+def test_content_filter_deny_wins_over_allow():
+    content_filter = ContentFilter(allow=["@public"], deny=["@draft"])
+    assert content_filter.matches(create_prompt_data(content="@public"))
+    assert not content_filter.matches(create_prompt_data(content="@public @draft"))
+
+
+def test_content_filter_invalid_pattern():
+    with pytest.raises(ValueError, match="Invalid content allow pattern"):
+        ContentFilter(allow=["("])
+
+
+def test_get_folder_paths_local_folders():
+    result = get_folder_paths(
+        folders=["/team", "/personal"],
+        git_url=None,
+        cache_dir=Path("/cache"),
+        auto_pull=False,
+    )
+    assert result == [Path("/team"), Path("/personal")]
+
+
+def test_get_folder_paths_no_config():
+    with pytest.raises(ValueError, match="Either folder or git-url"):
+        get_folder_paths(
+            folders=[],
+            git_url=None,
+            cache_dir=Path("/cache"),
+            auto_pull=False,
+        )
+
+
+def test_get_folder_paths_git_clones_once(tmp_path, monkeypatch):
+    cloned = []
+
+    def mock_clone(url, path, auto_pull, ref=None, git=None):
+        cloned.append(path)
+        path.mkdir(parents=True, exist_ok=True)
+
+    monkeypatch.setattr("shinkuro.loader.clone_or_update_repo", mock_clone)
+
+    result = get_folder_paths(
+        folders=["shared", "team"],
+        git_url="https://github.com/user/repo.git",
+        cache_dir=tmp_path,
+        auto_pull=False,
+    )
+
+    repo_path = tmp_path / "git" / "user" / "repo"
+    assert result == [repo_path / "shared", repo_path / "team"]
+    assert cloned == [repo_path]
+
+
+def test_parse_overrides():
+    assert parse_overrides(["role=assistant", "description_prefix=[beta] "]) == {
+        "role": "assistant",
+        "description_prefix": "[beta] ",
+    }
+
+
+def test_parse_overrides_value_with_equals():
+    assert parse_overrides(["title=a=b"]) == {"title": "a=b"}
+
+
+@pytest.mark.parametrize(
+    "value,message",
+    [
+        ("role", "expected key=value"),
+        ("content_type=text", "Unknown override key 'content_type'"),
+        ("role=system", "Invalid role 'system'"),
+    ],
+)
+def test_parse_overrides_invalid(value, message):
+    with pytest.raises(ValueError, match=message):
+        parse_overrides([value])
+
+
+def test_apply_overrides():
+    prompt_data = create_prompt_data(title="Original", description="Reviews code")
+    prompt_data.descriptions = {"en": "Reviews code", "zh": "审查代码"}
+
+    result = apply_overrides(
+        prompt_data,
+        parse_overrides(["role=assistant", "title=Changed", "description_prefix=X "]),
+    )
+
+    assert result.role == "assistant"
+    assert result.title == "Changed"
+    assert result.description == "X Reviews code"
+    assert result.descriptions == {"en": "X Reviews code", "zh": "X 审查代码"}
+    assert prompt_data.role == "user"
+    assert prompt_data.title == "Original"
+
+
+def test_apply_overrides_description_replaces_localized():
+    prompt_data = create_prompt_data(description="Reviews code")
+    prompt_data.descriptions = {"zh": "审查代码"}
+
+    result = apply_overrides(prompt_data, {"description": "Replaced"})
+
+    assert result.description == "Replaced"
+    assert result.localized_description("zh") == "Replaced"
+
+
+def test_apply_overrides_empty():
+    prompt_data = create_prompt_data()
+    assert apply_overrides(prompt_data, {}) is prompt_data
+
+
+def test_get_folder_path_git_with_ref(tmp_path, monkeypatch):
+    refs = []
+
+    def mock_clone(url, path, auto_pull, ref=None, git=None):
+        refs.append(ref)
+        path.mkdir(parents=True, exist_ok=True)
+
+    monkeypatch.setattr("shinkuro.loader.clone_or_update_repo", mock_clone)
+
+    result = get_folder_path(
+        folder=None,
+        git_url="https://github.com/user/repo.git",
+        cache_dir=tmp_path,
+        auto_pull=False,
+        git_ref="v1.2.0",
+    )
+
+    assert result == tmp_path / "git" / "user" / "repo@v1.2.0"
+    assert refs == ["v1.2.0"]
@@ -0,0 +1,192 @@
+"""Tests for main module."""
+
+import pytest
+import typer
+from fastmcp import FastMCP
+from typer.testing import CliRunner
+
+from shinkuro.main import app, cli
+from shinkuro.model import Transport
+
+
+def test_fail_on_empty_exits_when_no_prompts(tmp_path):
+    (tmp_path / "notes.txt").write_text("not a prompt")
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(folder=[str(tmp_path)], fail_on_empty=True)
+
+    assert exc_info.value.exit_code == 1
+
+
+def test_strict_names_exits_on_duplicate_names(tmp_path):
+    (tmp_path / "a").mkdir()
+    (tmp_path / "b").mkdir()
+    (tmp_path / "a" / "review.md").write_text("A")
+    (tmp_path / "b" / "review.md").write_text("B")
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(folder=[str(tmp_path)], strict_names=True)
+
+    assert exc_info.value.exit_code == 1
+
+
+def test_http_transport_runs_with_port(tmp_path, monkeypatch):
+    (tmp_path / "hello.md").write_text("Hello")
+    calls = []
+    monkeypatch.setattr(FastMCP, "run", lambda self, *a, **kw: calls.append(kw))
+
+    app(folder=[str(tmp_path)], transport=Transport.HTTP, port=9000)
+
+    assert calls == [{"transport": "http", "host": "127.0.0.1", "port": 9000}]
+
+
+def test_stdio_transport_is_default(tmp_path, monkeypatch):
+    (tmp_path / "hello.md").write_text("Hello")
+    calls = []
+    monkeypatch.setattr(FastMCP, "run", lambda self, *a, **kw: calls.append(kw))
+
+    app(folder=[str(tmp_path)])
+
+    assert calls == [{}]
+
+
+def test_render_prints_prompt_and_exits(tmp_path, capsys):
+    (tmp_path / "hello.md").write_text("Hello {user}")
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(
+            folder=[str(tmp_path)],
+            auto_discover_args=True,
+            render="hello",
+            arg=["user=Ann"],
+        )
+
+    assert exc_info.value.exit_code == 0
+    assert capsys.readouterr().out == "Hello Ann\n"
+
+
+def test_render_exits_on_missing_argument(tmp_path, capsys):
+    (tmp_path / "hello.md").write_text("Hello {user}")
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(folder=[str(tmp_path)], auto_discover_args=True, render="hello")
+
+    assert exc_info.value.exit_code == 1
+    assert "Error: Missing required arguments" in capsys.readouterr().err
+
+
+def test_check_reports_invalid_prompts(tmp_path, capsys, monkeypatch):
+    (tmp_path / "good.md").write_text(
+        "---\narguments:\n  - name: user\n---\nHello {user}"
+    )
+    (tmp_path / "broken.md").write_text("---\narguments:\n  - name: user\n---\nHi")
+    calls = []
+    monkeypatch.setattr(FastMCP, "run", lambda self, *a, **kw: calls.append(kw))
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(folder=[str(tmp_path)], check=True)
+
+    assert exc_info.value.exit_code == 1
+    out = capsys.readouterr().out
+    assert "[PASS]" in out and "good.md: loads as prompt 'good'" in out
+    assert "[FAIL]" in out and "broken.md: Content arguments" in out
+    assert calls == []
+
+
+def test_check_passes_valid_prompts(tmp_path, capsys):
+    (tmp_path / "hello.md").write_text("Hello")
+
+    with pytest.raises(typer.Exit) as exc_info:
+        app(folder=[str(tmp_path)], check=True)
+
+    assert exc_info.value.exit_code == 0
+    assert "all 1 checks passed" in capsys.readouterr().out
+
+
+def test_folder_env_var_keeps_spaces(tmp_path):
+    folder = tmp_path / "My Prompts"
+    folder.mkdir()
+    (folder / "hello.md").write_text("Hello")
+
+    result = CliRunner().invoke(cli, ["--render", "hello"], env={"FOLDER": str(folder)})
+
+    assert result.exit_code == 0, result.output
+    assert result.stdout == "Hello\n"
+
+
+def test_folder_env_var_lists_folders(tmp_path):
+    (tmp_path / "team").mkdir()
+    (tmp_path / "personal").mkdir()
+    (tmp_path / "team" / "hello.md").write_text("Team")
+    (tmp_path / "personal" / "hello.md").write_text("Personal")
+    folders = f"{tmp_path / 'team'},{tmp_path / 'personal'}"
+
+    result = CliRunner().invoke(cli, ["--render", "hello"], env={"FOLDER": folders})
+
+    assert result.stdout == "Personal\n"
+
+
+def test_folder_flag_is_not_split(tmp_path):
+    folder = tmp_path / "prompts,v2"
+    folder.mkdir()
+    (folder / "hello.md").write_text("Hello")
+
+    result = CliRunner().invoke(cli, ["--folder", str(folder), "--render", "hello"])
+
+    assert result.exit_code == 0, result.output
+    assert result.stdout == "Hello\n"
+
+
+def test_redact_env_var_keeps_spaces(tmp_path):
+    (tmp_path / "hello.md").write_text("Use api key: abc123 not key")
+    env = {"FOLDER": str(tmp_path), "REDACT": "api key: \\S+"}
+
+    result = CliRunner().invoke(cli, ["--render", "hello"], env=env)
+
+    assert result.stdout == "Use *** not key\n"
+
+
+def test_content_deny_env_var_keeps_spaces(tmp_path):
+    (tmp_path / "draft.md").write_text("DO NOT SHIP")
+    (tmp_path / "notes.md").write_text("NOT a draft")
+    env = {"FOLDER": str(tmp_path), "CONTENT_DENY": "DO NOT SHIP"}
+
+    result = CliRunner().invoke(cli, ["--check"], env=env)
+
+    assert "notes.md: loads as prompt 'notes'" in result.stdout
+    assert "draft.md" not in result.stdout
+
+
+def test_override_env_var_keeps_spaces(tmp_path, monkeypatch):
+    (tmp_path / "hello.md").write_text("Hello")
+    env = {
+        "FOLDER": str(tmp_path),
+        "OVERRIDE": "description_prefix=Team A: \nrole=assistant",
+    }
+
+    served = []
+    monkeypatch.setattr(FastMCP, "run", lambda self, *a, **kw: None)
+    add_prompt = FastMCP.add_prompt
+    monkeypatch.setattr(
+        FastMCP,
+        "add_prompt",
+        lambda self, prompt: served.append(prompt) or add_prompt(self, prompt),
+    )
+
+    result = CliRunner().invoke(cli, [], env=env)
+
+    assert result.exit_code == 0, result.output
+    assert [prompt.description for prompt in served] == ["Team A: Prompt from hello.md"]
+    assert served[0].role == "assistant"
+
+
+def test_include_env_var_keeps_spaces(tmp_path):
+    (tmp_path / "My Notes").mkdir()
+    (tmp_path / "My Notes" / "hello.md").write_text("Hello")
+    (tmp_path / "other.md").write_text("Other")
+    env = {"FOLDER": str(tmp_path), "INCLUDE": "My Notes/**"}
+
+    result = CliRunner().invoke(cli, ["--check"], env=env)
+
+    assert "hello.md: loads as prompt 'hello'" in result.stdout
+    assert "other.md" not in result.stdout
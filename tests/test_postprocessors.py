@@ -0,0 +1,68 @@
+"""Tests for postprocessors.py module."""
+
+import pytest
+from shinkuro.postprocessors import (
+    POST_PROCESSORS,
+    PostProcessorSpec,
+    apply_post_processors,
+    parse_post_processors,
+    register_post_processor,
+)
+
+
+@pytest.mark.parametrize(
+    "spec,text,expected",
+    [
+        ("code_fence", "a = 1", "```\na = 1\n```"),
+        ("code_fence(python)", "a = 1", "```python\na = 1\n```"),
+        ("code_fence(md)", "```\nx\n```", "````md\n```\nx\n```\n````"),
+        ("json_string", 'say "hi"\nnow', '"say \\"hi\\"\\nnow"'),
+        ("prefix(Note: )", "body", "Note: body"),
+        ("suffix( -- end)", "body", "body -- end"),
+        (
+            "single_line",
+            "line one\n  line two\n\nline three\n",
+            "line one line two line three",
+        ),
+    ],
+)
+def test_post_processor(spec, text, expected):
+    assert apply_post_processors(text, parse_post_processors([spec])) == expected
+
+
+def test_post_processors_apply_in_order():
+    processors = parse_post_processors(["prefix(> )", "code_fence(text)"])
+    reversed_processors = parse_post_processors(["code_fence(text)", "prefix(> )"])
+
+    assert apply_post_processors("hi", processors) == "```text\n> hi\n```"
+    assert apply_post_processors("hi", reversed_processors) == "> ```text\nhi\n```"
+
+
+def test_post_processor_parameter_with_parentheses():
+    processors = parse_post_processors(["suffix( (draft))"])
+
+    assert apply_post_processors("text", processors) == "text (draft)"
+
+
+@pytest.mark.parametrize(
+    "spec,message",
+    [
+        ("shout", "Unknown post-processor: shout"),
+        ("prefix", "Post-processor 'prefix' requires a parameter"),
+        ("suffix()", "Post-processor 'suffix' requires a parameter"),
+        ("json_string(x)", "Post-processor 'json_string' does not take a parameter"),
+        ("code fence", "Invalid post-processor"),
+    ],
+)
+def test_parse_post_processors_errors(spec, message):
+    with pytest.raises(ValueError, match=message):
+        parse_post_processors([spec])
+
+
+def test_register_post_processor():
+    register_post_processor("upper", PostProcessorSpec(lambda text, _: text.upper()))
+    try:
+        processors = parse_post_processors(["upper"])
+        assert apply_post_processors("hi", processors) == "HI"
+    finally:
+        del POST_PROCESSORS["upper"]
@@ -0,0 +1,76 @@
+"""Tests for file/read_errors.py module."""
+
+import errno
+import os
+import sys
+import pytest
+from shinkuro.file.read_errors import (
+    BROKEN_SYMLINK,
+    IO_ERROR,
+    NOT_FOUND,
+    PERMISSION_DENIED,
+    classify_read_error,
+    describe_read_error,
+)
+
+
+def _read_error(path) -> OSError:
+    try:
+        path.read_text()
+    except OSError as e:
+        return e
+    raise AssertionError(f"{path} was readable")
+
+
+def test_classify_not_found(tmp_path):
+    error = _read_error(tmp_path / "missing.md")
+    assert classify_read_error(error) == NOT_FOUND
+    assert "disappeared" in describe_read_error(error)
+
+
+@pytest.mark.skipif(sys.platform == "win32", reason="symlinks need privileges")
+def test_classify_broken_symlink(tmp_path):
+    link = tmp_path / "link.md"
+    link.symlink_to(tmp_path / "target.md")
+
+    error = _read_error(link)
+
+    assert classify_read_error(error) == BROKEN_SYMLINK
+    assert "target.md does not exist" in describe_read_error(error)
+
+
+def test_classify_permission_denied(tmp_path):
+    path = tmp_path / "secret.md"
+    path.write_text("secret")
+    error = PermissionError(errno.EACCES, "Permission denied", str(path))
+
+    assert classify_read_error(error) == PERMISSION_DENIED
+    description = describe_read_error(error)
+    assert description.startswith(PERMISSION_DENIED)
+    if hasattr(os, "getuid"):
+        assert f"owner uid {os.getuid()}" in description
+        assert "mode -rw" in description
+
+
+@pytest.mark.skipif(
+    not hasattr(os, "getuid") or os.getuid() == 0,
+    reason="needs a unix user that file modes apply to",
+)
+def test_classify_real_permission_denied(tmp_path):
+    path = tmp_path / "secret.md"
+    path.write_text("secret")
+    path.chmod(0)
+
+    try:
+        error = _read_error(path)
+    finally:
+        path.chmod(0o600)
+
+    assert classify_read_error(error) == PERMISSION_DENIED
+    assert "mode ----------" in describe_read_error(error)
+
+
+def test_classify_other_io_error(tmp_path):
+    error = IsADirectoryError(errno.EISDIR, "Is a directory", str(tmp_path))
+    assert classify_read_error(error) == IO_ERROR
+    assert describe_read_error(error) == "io error: Is a directory"
@@ -0,0 +1,79 @@
+"""Tests for render.py module."""
+
+import pytest
+from types import SimpleNamespace
+
+from shinkuro.errors import MissingRequired
+from shinkuro.formatters import BraceFormatter
+from shinkuro.prompts.markdown import MarkdownPrompt
+from shinkuro.render import complete_prompt_names, parse_render_args, render_prompt
+from .fixtures import create_argument, create_prompt_data
+
+
+def test_parse_render_args():
+    assert parse_render_args(["user=Ann", "query=a=b", "user=Bob"]) == {
+        "user": "Bob",
+        "query": "a=b",
+    }
+
+
+@pytest.mark.parametrize("value", ["user", "=Ann"])
+def test_parse_render_args_invalid(value):
+    with pytest.raises(ValueError, match="expected key=value"):
+        parse_render_args([value])
+
+
+def test_render_prompt_single_message():
+    prompt = MarkdownPrompt.from_prompt_data(
+        create_prompt_data(arguments=[create_argument("user")], content="Hello {user}"),
+        BraceFormatter(),
+    )
+
+    assert render_prompt(prompt, {"user": "Ann"}) == "Hello Ann"
+
+
+def test_render_prompt_labels_roles_of_several_messages():
+    prompt = MarkdownPrompt.from_prompt_data(
+        create_prompt_data(content="Question\n<!-- role: assistant -->\nAnswer"),
+        BraceFormatter(),
+    )
+
+    assert render_prompt(prompt, {}) == (
+        "--- user ---\nQuestion\n\n--- assistant ---\nAnswer"
+    )
+
+
+def test_render_prompt_missing_required():
+    prompt = MarkdownPrompt.from_prompt_data(
+        create_prompt_data(arguments=[create_argument("user")], content="Hello {user}"),
+        BraceFormatter(),
+    )
+
+    with pytest.raises(MissingRequired):
+        render_prompt(prompt, {})
+
+
+def _completion_context(**params):
+    return SimpleNamespace(params=params)
+
+
+def test_complete_prompt_names_from_folders(tmp_path):
+    (tmp_path / "review.md").write_text("Review")
+    (tmp_path / "refactor.md").write_text("Refactor")
+    (tmp_path / "summarize.md").write_text("Summarize")
+
+    ctx = _completion_context(folder=[str(tmp_path)])
+
+    assert complete_prompt_names(ctx, "re") == ["refactor", "review"]
+
+
+def test_complete_prompt_names_uncached_repository(tmp_path):
+    ctx = _completion_context(
+        git_url="https://github.com/owner/repo.git", cache_dir=str(tmp_path)
+    )
+
+    assert complete_prompt_names(ctx, "") == []
+
+
+def test_complete_prompt_names_invalid_git_url():
+    assert complete_prompt_names(_completion_context(git_url="invalid-url"), "") == []
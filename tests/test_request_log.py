@@ -0,0 +1,48 @@
+"""Tests for request_log.py module."""
+
+import pytest
+from fastmcp.server.middleware import MiddlewareContext
+from mcp.types import GetPromptRequestParams, ListPromptsRequest
+from shinkuro.request_log import RequestLogMiddleware
+from .mocks import MockLogger
+
+
+class StepClock:
+    """Clock advancing by a fixed step on every reading."""
+
+    def __init__(self, step: float):
+        self.now = 0.0
+        self.step = step
+
+    def __call__(self) -> float:
+        self.now += self.step
+        return self.now
+
+
+@pytest.mark.asyncio
+async def test_request_log_middleware_logs_prompt_get():
+    logger = MockLogger()
+    middleware = RequestLogMiddleware(logger, StepClock(0.012))
+    context = MiddlewareContext(
+        message=GetPromptRequestParams(name="review"), method="prompts/get"
+    )
+
+    async def call_next(context):
+        return "result"
+
+    assert await middleware.on_request(context, call_next) == "result"
+    assert logger.debugs == ["handled prompts/get 'review' in 12ms"]
+
+
+@pytest.mark.asyncio
+async def test_request_log_middleware_logs_failures():
+    logger = MockLogger()
+    middleware = RequestLogMiddleware(logger, StepClock(0.002))
+    context = MiddlewareContext(message=ListPromptsRequest(), method="prompts/list")
+
+    async def call_next(context):
+        raise ValueError("boom")
+
+    with pytest.raises(ValueError):
+        await middleware.on_request(context, call_next)
+    assert logger.debugs == ["failed prompts/list in 2ms"]
@@ -1,5 +1,7 @@
 """Tests for file/scan.py module."""
 
+import pytest
+from collections import Counter
 from pathlib import Path
 from shinkuro.file.scan import (
     scan_markdown_files,
@@ -7,8 +9,12 @@ from shinkuro.file.scan import (
     _parse_argument,
     _parse_arguments,
     _parse_markdown_file,
+    _is_sync_artifact,
+    scan_markdown_folders,
+    parse_markdown_file,
 )
-from shinkuro.model import Argument
+from shinkuro.model import Argument, ArgumentType, FormatterType
+from shinkuro.timing import TimingCollector
 from .mocks import MockFileSystem, MockLogger
 from .fixtures import create_markdown_file_content, create_test_files
 
@@ -156,8 +162,8 @@ def test_scan_markdown_files_with_error():
     logger = MockLogger()
     results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
     assert len(results) == 0
-    assert len(logger.warnings) == 1
-    assert "failed to process" in logger.warnings[0]
+    assert len(logger.errors) == 1
+    assert "failed to process" in logger.errors[0]
 
 
 def test_parse_argument_non_string_name():
@@ -185,9 +191,83 @@ def test_parse_argument_non_string_default():
     )
     assert arg is not None
     assert arg.default == "123"
+    assert logger.warnings == []
+
+
+def test_parse_argument_structured_default():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "test", "default": [1, 2]}, Path("/test.md"), logger=logger
+    )
+    assert arg is not None
+    assert arg.default == "[1, 2]"
     assert len(logger.warnings) == 1
 
 
+def test_parse_argument_choices():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "level", "choices": ["low", "high", 3]},
+        Path("/test.md"),
+        logger=logger,
+    )
+    assert arg is not None
+    assert arg.choices == ["low", "high", "3"]
+    assert len(logger.warnings) == 0
+
+
+def test_parse_argument_enum_alias():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "level", "enum": ["low", "high"]}, Path("/test.md"), logger=logger
+    )
+    assert arg is not None
+    assert arg.choices == ["low", "high"]
+    assert len(logger.warnings) == 0
+
+
+def test_parse_argument_choices_and_enum():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "level", "choices": ["low"], "enum": ["high"]},
+        Path("/test.md"),
+        logger=logger,
+    )
+    assert arg is not None
+    assert arg.choices == ["low"]
+    assert "both 'choices' and 'enum'" in logger.warnings[0]
+
+
+def test_parse_argument_type():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "count", "type": "number"}, Path("/test.md"), logger=logger
+    )
+    assert arg is not None
+    assert arg.type == ArgumentType.NUMBER
+    assert len(logger.warnings) == 0
+
+
+def test_parse_argument_invalid_type():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "count", "type": "integer"}, Path("/test.md"), logger=logger
+    )
+    assert arg is not None
+    assert arg.type == ArgumentType.STRING
+    assert "'type' field" in logger.warnings[0]
+
+
+def test_parse_argument_choices_not_list():
+    logger = MockLogger()
+    arg = _parse_argument(
+        {"name": "level", "choices": "low"}, Path("/test.md"), logger=logger
+    )
+    assert arg is not None
+    assert arg.choices == []
+    assert "'choices' field in /test.md is not a list" in logger.warnings[0]
+
+
 def test_parse_markdown_file_skip_frontmatter():
     content = """---
 name: "custom-name"
@@ -239,3 +319,781 @@ Hello world!""",
     assert greet.title == "greet"
     assert greet.description == "Prompt from nested/greet.md"
     assert greet.arguments == []
+
+
+def test_parse_markdown_file_reads_single_file():
+    fs = MockFileSystem(
+        create_test_files({"/test/nested/greet.md": "---\ntitle: Greet\n---\nHi!"})
+    )
+
+    result = parse_markdown_file(
+        Path("/test/nested/greet.md"), Path("/test"), False, fs=fs
+    )
+
+    assert result.name == "greet"
+    assert result.title == "Greet"
+    assert result.content == "Hi!"
+    assert result.path == Path("/test/nested/greet.md")
+
+
+FRONTMATTER_FORMATS = {
+    "yaml": """---
+name: greeting
+title: Greeting
+description: Greet a user
+arguments:
+  - name: user
+    description: User name
+    default: World
+---
+Hello {user}""",
+    "toml": """+++
+name = "greeting"
+title = "Greeting"
+description = "Greet a user"
+
+[[arguments]]
+name = "user"
+description = "User name"
+default = "World"
++++
+Hello {user}""",
+    "json": """{
+  "name": "greeting",
+  "title": "Greeting",
+  "description": "Greet a user",
+  "arguments": [
+    {"name": "user", "description": "User name", "default": "World"}
+  ]
+}
+Hello {user}""",
+    "delimited json": """;;;
+{
+  "name": "greeting",
+  "title": "Greeting",
+  "description": "Greet a user",
+  "arguments": [
+    {"name": "user", "description": "User name", "default": "World"}
+  ]
+}
+;;;
+Hello {user}""",
+}
+
+
+@pytest.mark.parametrize("fmt", ["toml", "json", "delimited json"])
+def test_parse_markdown_file_frontmatter_formats(fmt):
+    def parse(content):
+        return _parse_markdown_file(
+            Path("/test/file.md"), Path("/test"), content, False, logger=MockLogger()
+        )
+
+    expected = parse(FRONTMATTER_FORMATS["yaml"])
+    result = parse(FRONTMATTER_FORMATS[fmt])
+
+    assert result == expected
+    assert result.name == "greeting"
+    assert result.arguments == [Argument("user", "User name", "World")]
+    assert result.content == "Hello {user}"
+
+
+def test_parse_markdown_file_body_starting_with_brace_line():
+    content = """{
+  "query": "{topic}",
+  "limit": {limit}
+}
+Send the request above."""
+    logger = MockLogger()
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.name == "file"
+    assert result.content == content
+    assert len(logger.warnings) == 1
+    assert "invalid JSON frontmatter in /test/file.md" in logger.warnings[0]
+
+
+def test_parse_markdown_file_malformed_leading_json_warns():
+    content = '{\n  "name": "greeting",\n  "title": \n}\nHello'
+    logger = MockLogger()
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.name == "file"
+    assert result.content == content
+    assert logger.warnings[0].startswith("invalid JSON frontmatter in /test/file.md: ")
+    assert "using the whole file as prompt content" in logger.warnings[0]
+
+
+def test_scan_markdown_files_strict_frontmatter_skips_malformed_json():
+    files = {"/test/bad.md": '{\n  "name": \n}\nHello', "/test/ok.md": "Hello"}
+    fs = MockFileSystem(create_test_files(files))
+
+    lenient_logger = MockLogger()
+    lenient = list(
+        scan_markdown_files(Path("/test"), False, fs=fs, logger=lenient_logger)
+    )
+    strict_logger = MockLogger()
+    strict = list(
+        scan_markdown_files(
+            Path("/test"),
+            False,
+            strict_frontmatter=True,
+            fs=fs,
+            logger=strict_logger,
+        )
+    )
+
+    assert sorted(r.name for r in lenient) == ["bad", "ok"]
+    assert "invalid JSON frontmatter in /test/bad.md" in lenient_logger.warnings[0]
+    assert [r.name for r in strict] == ["ok"]
+    assert "failed to process /test/bad.md" in strict_logger.errors[0]
+
+
+def test_parse_markdown_file_unclosed_leading_brace_is_body():
+    content = "{\nnot frontmatter"
+    logger = MockLogger()
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.content == content
+    assert logger.warnings == []
+
+
+SCALAR_DEFAULT_FORMATS = {
+    "yaml": "---\narguments:\n  - name: flag\n    default: true\n"
+    "  - name: count\n    default: 3\n---\n{flag} {count}",
+    "toml": '+++\n[[arguments]]\nname = "flag"\ndefault = true\n\n'
+    '[[arguments]]\nname = "count"\ndefault = 3\n+++\n{flag} {count}',
+    "json": '{\n"arguments": [{"name": "flag", "default": true}, '
+    '{"name": "count", "default": 3}]\n}\n{flag} {count}',
+}
+
+
+@pytest.mark.parametrize("fmt", list(SCALAR_DEFAULT_FORMATS))
+def test_parse_markdown_file_scalar_defaults_match_across_formats(fmt):
+    logger = MockLogger()
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"),
+        Path("/test"),
+        SCALAR_DEFAULT_FORMATS[fmt],
+        False,
+        logger=logger,
+    )
+
+    assert [arg.default for arg in result.arguments] == ["true", "3"]
+    assert logger.warnings == []
+
+
+@pytest.mark.parametrize("fmt", list(FRONTMATTER_FORMATS))
+def test_parse_markdown_file_windows_line_endings_and_bom(fmt):
+    content = "\ufeff" + FRONTMATTER_FORMATS[fmt].replace("\n", "\r\n")
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=MockLogger()
+    )
+
+    assert result.name == "greeting"
+    assert result.title == "Greeting"
+    assert result.arguments == [Argument("user", "User name", "World")]
+    assert result.content == "Hello {user}"
+
+
+def test_parse_markdown_file_skip_frontmatter_strips_bom():
+    result = _parse_markdown_file(
+        Path("/test/file.md"),
+        Path("/test"),
+        "\ufeffHello\r\nworld",
+        True,
+        logger=MockLogger(),
+    )
+
+    assert result.content == "Hello\nworld"
+
+
+def test_parse_markdown_file_variable_format():
+    logger = MockLogger()
+    content = "---\nvariable_format: dollar\n---\nHello $user"
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.variable_format == FormatterType.DOLLAR
+    assert logger.warnings == []
+
+
+def test_parse_markdown_file_invalid_variable_format():
+    logger = MockLogger()
+    content = "---\nvariable_format: mustache\n---\nHello {user}"
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.variable_format is None
+    assert "'variable_format' field in /test/file.md" in logger.warnings[0]
+
+
+def test_parse_markdown_file_yaml_truthy_values_stay_literal():
+    logger = MockLogger()
+    content = """---
+title: on
+arguments:
+  - name: confirm
+    default: no
+  - name: enabled
+    default: true
+---
+{confirm} {enabled}"""
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.title == "on"
+    assert result.arguments[0].default == "no"
+    assert result.arguments[1].default == "true"
+    assert logger.warnings == []
+
+
+def test_parse_markdown_file_yaml_number_default_keeps_text():
+    logger = MockLogger()
+    content = """---
+arguments:
+  - name: price
+    default: 1.50
+  - name: port
+    default: 0x1F
+---
+{price} {port}"""
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.arguments[0].default == "1.50"
+    assert result.arguments[1].default == "0x1F"
+    assert logger.warnings == []
+
+
+def test_parse_markdown_file_post_processors():
+    logger = MockLogger()
+    content = "---\npost:\n  - code_fence(python)\n  - json_string\n---\nprint(1)"
+
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.post == ["code_fence(python)", "json_string"]
+
+
+def test_scan_markdown_files_unknown_post_processor():
+    fs = MockFileSystem(
+        create_test_files({"/test/bad.md": "---\npost: [shout]\n---\nContent"})
+    )
+    logger = MockLogger()
+
+    results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
+
+    assert len(results) == 0
+    assert "failed to process /test/bad.md" in logger.errors[0]
+    assert "Unknown post-processor: shout" in logger.errors[0]
+
+
+def test_parse_markdown_file_localized_description():
+    logger = MockLogger()
+    content = """---
+description:
+  en: Review code
+  zh-CN: 审查代码
+  ja: コードレビュー
+---
+Review"""
+
+    result = _parse_markdown_file(
+        Path("/test/review.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.description == "Review code"
+    assert result.descriptions == {
+        "en": "Review code",
+        "zh-CN": "审查代码",
+        "ja": "コードレビュー",
+    }
+    assert result.localized_description("ja") == "コードレビュー"
+    assert result.localized_description("zh_cn") == "审查代码"
+    assert result.localized_description("en-US") == "Review code"
+    assert result.localized_description("fr") == "Review code"
+    assert result.localized_description(None) == "Review code"
+    assert len(logger.warnings) == 0
+
+
+def test_parse_markdown_file_localized_description_without_english():
+    logger = MockLogger()
+    content = "---\ndescription:\n  de: Code prüfen\n  fr: 123\n---\nReview"
+
+    result = _parse_markdown_file(
+        Path("/test/review.md"), Path("/test"), content, False, logger=logger
+    )
+
+    assert result.description == "Code prüfen"
+    assert result.localized_description("fr") == "123"
+    assert len(logger.warnings) == 1
+
+
+def test_scan_markdown_files_skips_hidden_by_default():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/visible.md": "Content",
+                "/test/.hidden.md": "Content",
+                "/test/.stversions/old.md": "Content",
+            }
+        )
+    )
+    logger = MockLogger()
+    results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
+    assert [r.name for r in results] == ["visible"]
+    assert logger.warnings == []
+
+
+def test_scan_markdown_files_include_hidden():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/visible.md": "Content",
+                "/test/.hidden.md": "Content",
+                "/test/.stversions/old.md": "Content",
+            }
+        )
+    )
+    logger = MockLogger()
+    results = list(
+        scan_markdown_files(
+            Path("/test"), False, include_hidden=True, fs=fs, logger=logger
+        )
+    )
+    assert sorted(r.name for r in results) == [".hidden", "old", "visible"]
+
+
+@pytest.mark.parametrize(
+    "name",
+    [
+        "notes.sync-conflict-20240101-120000-ABCDEFG.md",
+        "notes.md~",
+        "#notes.md#",
+        ".#notes.md",
+    ],
+)
+def test_is_sync_artifact(name):
+    assert _is_sync_artifact(Path("/test") / name)
+
+
+def test_is_sync_artifact_regular_file():
+    assert not _is_sync_artifact(Path("/test/notes.md"))
+
+
+def test_scan_markdown_files_reports_sync_artifacts():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/notes.md": "Original",
+                "/test/notes.sync-conflict-20240101-120000-ABCDEFG.md": "Changed",
+            }
+        )
+    )
+    logger = MockLogger()
+    results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
+    assert [r.name for r in results] == ["notes"]
+    assert len(logger.warnings) == 1
+    assert "skipped" in logger.warnings[0]
+    assert logger.warnings[0].endswith(": sync artifact")
+
+
+def test_scan_markdown_files_notes_identical_sync_conflict():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/notes.md": "Same",
+                "/test/notes.sync-conflict-20240101-120000-ABCDEFG.md": "Same",
+            }
+        )
+    )
+    logger = MockLogger()
+    results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
+    assert len(results) == 1
+    assert len(logger.warnings) == 1
+    assert "identical to notes.md (harmless)" in logger.warnings[0]
+
+
+class UnreadableFileSystem(MockFileSystem):
+    """Mock file system raising a given error for some files."""
+
+    def __init__(self, files: dict[Path, str], errors: dict[Path, OSError]):
+        super().__init__(files)
+        self.errors = errors
+
+    def read_text(self, path: Path) -> str:
+        if path in self.errors:
+            raise self.errors[path]
+        return super().read_text(path)
+
+
+def test_scan_markdown_files_classifies_read_errors():
+    denied = Path("/test/denied.md")
+    vanished = Path("/test/vanished.md")
+    fs = UnreadableFileSystem(
+        create_test_files(
+            {
+                "/test/ok.md": "Content",
+                "/test/denied.md": "",
+                "/test/vanished.md": "",
+            }
+        ),
+        {
+            denied: PermissionError(13, "Permission denied"),
+            vanished: FileNotFoundError(2, "No such file or directory"),
+        },
+    )
+    logger = MockLogger()
+    read_errors: Counter[str] = Counter()
+
+    results = list(
+        scan_markdown_files(
+            Path("/test"), False, read_errors=read_errors, fs=fs, logger=logger
+        )
+    )
+
+    assert [r.name for r in results] == ["ok"]
+    assert read_errors == {"permission denied": 1, "not found": 1}
+    assert f"failed to read {denied}: permission denied" in logger.errors
+    assert logger.errors[-1] == (
+        "2 file(s) could not be read: 1 permission denied, 1 not found"
+    )
+
+
+def test_scan_markdown_files_records_failures():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/ok.md": "Content",
+                "/test/broken.md": "---\nname: [unclosed\n---\nContent",
+            }
+        )
+    )
+    failures: list[tuple[Path, str]] = []
+
+    results = list(
+        scan_markdown_files(
+            Path("/test"), False, failures=failures, fs=fs, logger=MockLogger()
+        )
+    )
+
+    assert [r.name for r in results] == ["ok"]
+    assert [path for path, _ in failures] == [Path("/test/broken.md")]
+
+
+def test_scan_markdown_files_records_timings():
+    files = {
+        "/test/ok.md": "Content",
+        "/test/café.md": "Überblick",
+        "/test/broken.md": "---\nname: [unclosed\n---\nContent",
+    }
+    fs = MockFileSystem(create_test_files(files))
+    timings = TimingCollector()
+
+    list(
+        scan_markdown_files(
+            Path("/test"), False, timings=timings, fs=fs, logger=MockLogger()
+        )
+    )
+
+    phases = {phase.name: phase for phase in timings.phases}
+    assert phases["scan"].unit == "files"
+    assert phases["scan"].count == 3
+    assert phases["scan"].bytes == sum(len(c.encode()) for c in files.values())
+    # The broken file is read but fails to parse
+    assert phases["parse"].count == 2
+    assert phases["parse"].bytes == 0
+
+
+def test_scan_markdown_folders_later_folder_overrides(tmp_path):
+    team = tmp_path / "team"
+    personal = tmp_path / "personal"
+    team.mkdir()
+    personal.mkdir()
+    (team / "review.md").write_text("Team review")
+    (team / "commit.md").write_text("Team commit")
+    (personal / "review.md").write_text("My review")
+    logger = MockLogger()
+
+    results = scan_markdown_folders([team, personal], False, logger=logger)
+
+    prompts = {
+        prompt_data.name: (folder, prompt_data) for folder, prompt_data in results
+    }
+    assert set(prompts) == {"review", "commit"}
+    assert prompts["review"][0] == personal
+    assert prompts["review"][1].content == "My review"
+    assert prompts["commit"][0] == team
+    assert logger.warnings == [
+        f"prompt 'review' from {personal / 'review.md'} overrides {team / 'review.md'}"
+    ]
+
+
+def test_scan_markdown_folders_skips_missing_folder(tmp_path):
+    team = tmp_path / "team"
+    team.mkdir()
+    (team / "review.md").write_text("Team review")
+    missing = tmp_path / "missing"
+    logger = MockLogger()
+
+    results = scan_markdown_folders([missing, team], False, logger=logger)
+
+    assert [prompt_data.name for _, prompt_data in results] == ["review"]
+    assert len(logger.warnings) == 1
+    assert "does not exist" in logger.warnings[0]
+
+
+def test_scan_markdown_files_namespace_by_dir():
+    fs = MockFileSystem(
+        create_test_files(
+            {
+                "/test/think.md": "Think",
+                "/test/coding/review.md": "Code review",
+                "/test/writing/review.md": "Writing review",
+                "/test/writing/blog/draft.md": "---\nname: blog-draft\n---\nDraft",
+            }
+        )
+    )
+
+    results = list(
+        scan_markdown_files(Path("/test"), False, namespace_separator="/", fs=fs)
+    )
+
+    assert sorted(prompt_data.name for prompt_data in results) == [
+        "blog-draft",
+        "coding/review",
+        "think",
+        "writing/review",
+    ]
+
+
+def test_scan_markdown_files_namespace_separator():
+    fs = MockFileSystem(create_test_files({"/test/coding/go/review.md": "Review"}))
+
+    results = list(
+        scan_markdown_files(Path("/test"), True, namespace_separator="_", fs=fs)
+    )
+
+    assert [prompt_data.name for prompt_data in results] == ["coding_go_review"]
+
+
+def test_scan_markdown_folders_warns_duplicates_after_namespacing(tmp_path):
+    (tmp_path / "coding").mkdir()
+    (tmp_path / "coding" / "review.md").write_text("Nested review")
+    (tmp_path / "coding_review.md").write_text("Top-level review")
+    logger = MockLogger()
+
+    results = scan_markdown_folders(
+        [tmp_path], False, namespace_separator="_", logger=logger
+    )
+
+    assert [prompt_data.name for _, prompt_data in results] == ["coding_review"]
+    assert results[0][1].content == "Nested review"
+    assert len(logger.warnings) == 1
+    assert "duplicate prompt name 'coding_review'" in logger.warnings[0]
+
+
+@pytest.mark.parametrize("reverse", [False, True])
+def test_scan_markdown_folders_duplicate_names_first_path_wins(reverse):
+    files = {
+        "/test/b/review.md": "B review",
+        "/test/a/review.md": "A review",
+        "/test/c/review.md": "C review",
+    }
+    items = sorted(files.items(), reverse=reverse)
+    fs = MockFileSystem(create_test_files(dict(items)))
+    logger = MockLogger()
+    duplicates: list[str] = []
+
+    results = scan_markdown_folders(
+        [Path("/test")], False, duplicates=duplicates, fs=fs, logger=logger
+    )
+
+    assert len(results) == 1
+    assert results[0][1].content == "A review"
+    assert results[0][1].path == Path("/test/a/review.md")
+    assert duplicates == ["review", "review"]
+    assert len(logger.warnings) == 2
+    assert all("duplicate prompt name 'review'" in w for w in logger.warnings)
+
+
+def test_scan_markdown_files_shinkuroignore(tmp_path):
+    (tmp_path / ".shinkuroignore").write_text(
+        "# not prompts\nREADME.md\ndrafts/**\n!drafts/ready.md\n"
+    )
+    (tmp_path / "README.md").write_text("About this repo")
+    (tmp_path / "review.md").write_text("Review the code")
+    (tmp_path / "drafts").mkdir()
+    (tmp_path / "drafts" / "wip.md").write_text("Not ready")
+    (tmp_path / "drafts" / "ready.md").write_text("Ready")
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "README.md").write_text("Nested readme")
+
+    logger = MockLogger()
+
+    prompts = list(scan_markdown_files(tmp_path, False, logger=logger))
+
+    assert sorted(p.name for p in prompts) == ["ready", "review"]
+    assert f"skipped {tmp_path / 'README.md'}: matched by .shinkuroignore" in (
+        logger.debugs
+    )
+
+
+def test_scan_markdown_files_include_patterns(tmp_path):
+    (tmp_path / "prompts" / "coding").mkdir(parents=True)
+    (tmp_path / "prompts" / "review.md").write_text("Review the code")
+    (tmp_path / "prompts" / "coding" / "refactor.md").write_text("Refactor")
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "guide.md").write_text("How to write prompts")
+    (tmp_path / "README.md").write_text("About this repo")
+    (tmp_path / "extra.md").write_text("Extra prompt")
+
+    logger = MockLogger()
+
+    prompts = list(
+        scan_markdown_files(
+            tmp_path, False, include=["prompts/**/*.md", "/extra.md"], logger=logger
+        )
+    )
+
+    assert sorted(p.name for p in prompts) == ["extra", "refactor", "review"]
+    assert f"skipped {tmp_path / 'README.md'}: not matched by --include" in (
+        logger.debugs
+    )
+
+
+def test_scan_markdown_files_include_patterns_use_gitignore_syntax(tmp_path):
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "guide.md").write_text("Guide")
+    (tmp_path / "review.md").write_text("Review")
+
+    nested = list(scan_markdown_files(tmp_path, False, include=["*.md"]))
+    root_only = list(scan_markdown_files(tmp_path, False, include=["/*.md"]))
+
+    assert sorted(p.name for p in nested) == ["guide", "review"]
+    assert [p.name for p in root_only] == ["review"]
+
+
+def test_scan_markdown_files_without_include_loads_all(tmp_path):
+    (tmp_path / "docs").mkdir()
+    (tmp_path / "docs" / "guide.md").write_text("Guide")
+    (tmp_path / "review.md").write_text("Review")
+
+    prompts = list(scan_markdown_files(tmp_path, False, include=[]))
+
+    assert sorted(p.name for p in prompts) == ["guide", "review"]
+
+
+def test_scan_markdown_files_ignore_patterns_are_relative_to_folder(tmp_path):
+    (tmp_path / ".shinkuroignore").write_text("/drafts/\n")
+    (tmp_path / "drafts").mkdir()
+    (tmp_path / "drafts" / "wip.md").write_text("Not ready")
+    (tmp_path / "team").mkdir()
+    (tmp_path / "team" / "drafts").mkdir()
+    (tmp_path / "team" / "drafts" / "plan.md").write_text("Plan")
+
+    prompts = list(scan_markdown_files(tmp_path, False, logger=MockLogger()))
+
+    assert [p.name for p in prompts] == ["plan"]
+
+
+def test_scan_markdown_folders_duplicate_names_in_subfolders(tmp_path):
+    (tmp_path / "coding").mkdir()
+    (tmp_path / "writing").mkdir()
+    (tmp_path / "coding" / "review.md").write_text("Code review")
+    (tmp_path / "writing" / "review.md").write_text("Writing review")
+    logger = MockLogger()
+
+    results = scan_markdown_folders([tmp_path], False, logger=logger)
+
+    assert [prompt_data.content for _, prompt_data in results] == ["Code review"]
+    assert logger.warnings == [
+        f"duplicate prompt name 'review' in {tmp_path / 'coding' / 'review.md'} "
+        f"and {tmp_path / 'writing' / 'review.md'}, "
+        f"using {tmp_path / 'coding' / 'review.md'}"
+    ]
+
+
+def test_parse_markdown_file_messages():
+    content = """---
+arguments:
+  - name: topic
+messages:
+  - role: user
+    content: Explain {topic}.
+  - role: assistant
+    content: What do you know about {topic}?
+  - content: Nothing yet.
+---
+"""
+    result = _parse_markdown_file(
+        Path("/test/file.md"), Path("/test"), content, False, logger=MockLogger()
+    )
+
+    assert result.content == (
+        "<!-- role: user -->\nExplain {topic}.\n\n"
+        "<!-- role: assistant -->\nWhat do you know about {topic}?\n\n"
+        "<!-- role: user -->\nNothing yet."
+    )
+
+
+@pytest.mark.parametrize(
+    "messages, error",
+    [
+        ("messages:\n  - role: system\n    content: Hi", "Invalid role 'system'"),
+        ("messages:\n  - role: user", "must have a string 'content'"),
+        ("messages: hello", "must be a non-empty list"),
+    ],
+)
+def test_parse_markdown_file_invalid_messages(messages, error):
+    content = f"---\n{messages}\n---\n"
+
+    with pytest.raises(ValueError, match=error):
+        _parse_markdown_file(
+            Path("/test/file.md"), Path("/test"), content, False, logger=MockLogger()
+        )
+
+
+def test_parse_markdown_file_messages_with_body():
+    content = "---\nmessages:\n  - content: Hi\n---\nBody"
+
+    with pytest.raises(ValueError, match="cannot be combined with a markdown body"):
+        _parse_markdown_file(
+            Path("/test/file.md"), Path("/test"), content, False, logger=MockLogger()
+        )
+
+
+def test_scan_markdown_files_invalid_message_role_reports_path():
+    fs = MockFileSystem(
+        create_test_files(
+            {"/test/chat.md": "---\nmessages:\n  - role: bot\n    content: Hi\n---\n"}
+        )
+    )
+    logger = MockLogger()
+
+    results = list(scan_markdown_files(Path("/test"), False, fs=fs, logger=logger))
+
+    assert results == []
+    assert len(logger.errors) == 1
+    assert "/test/chat.md" in logger.errors[0]
+    assert "Invalid role 'bot'" in logger.errors[0]
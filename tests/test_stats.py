@@ -0,0 +1,69 @@
+"""Tests for stats.py module."""
+
+from shinkuro.formatters import BraceFormatter
+from shinkuro.model import FormatterType
+from shinkuro.stats import collect_argument_usage
+from .fixtures import create_argument, create_prompt_data
+
+
+def test_collect_argument_usage_reports_unused_argument():
+    prompts = [
+        create_prompt_data(
+            name="greeting",
+            arguments=[
+                create_argument("user"),
+                create_argument("project"),
+            ],
+            content="Hello {user}!",
+        ),
+        create_prompt_data(
+            name="plain",
+            arguments=[create_argument("topic")],
+            content="Talk about {topic}",
+        ),
+    ]
+
+    stats = collect_argument_usage(prompts, BraceFormatter())
+
+    greeting = stats["prompts"][0]
+    assert greeting["name"] == "greeting"
+    assert greeting["used"] == ["user"]
+    assert greeting["unused"] == ["project"]
+    assert greeting["undeclared"] == []
+    assert stats["prompts"][1]["unused"] == []
+    assert stats["totals"] == {
+        "prompts": 2,
+        "prompts_with_unused": 1,
+        "declared_arguments": 3,
+        "unused_arguments": 1,
+    }
+
+
+def test_collect_argument_usage_reports_undeclared_variable():
+    prompts = [create_prompt_data(content="Hello {user}!")]
+
+    stats = collect_argument_usage(prompts, BraceFormatter())
+
+    assert stats["prompts"][0]["undeclared"] == ["user"]
+    assert stats["totals"]["unused_arguments"] == 0
+
+
+def test_collect_argument_usage_invalid_template():
+    prompts = [create_prompt_data(content="Hello {123}")]
+
+    stats = collect_argument_usage(prompts, BraceFormatter())
+
+    assert "Invalid variable name" in stats["prompts"][0]["error"]
+    assert stats["totals"]["prompts"] == 1
+
+
+def test_collect_argument_usage_uses_prompt_variable_format():
+    prompt_data = create_prompt_data(
+        arguments=[create_argument("user", "User", None)], content="Hello $user"
+    )
+    prompt_data.variable_format = FormatterType.DOLLAR
+
+    report = collect_argument_usage([prompt_data], BraceFormatter())
+
+    assert report["prompts"][0]["used"] == ["user"]
+    assert report["prompts"][0]["unused"] == []
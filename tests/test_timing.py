@@ -0,0 +1,144 @@
+"""Tests for startup timing collection."""
+
+import threading
+
+from shinkuro.timing import (
+    Phase,
+    TimingCollector,
+    format_bytes,
+    format_duration,
+    format_phase,
+)
+
+
+class FakeClock:
+    """Clock that advances only when told to."""
+
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self) -> float:
+        return self.now
+
+    def advance(self, seconds: float) -> None:
+        self.now += seconds
+
+
+def test_span_records_elapsed_time():
+    clock = FakeClock()
+    timings = TimingCollector(clock)
+
+    with timings.span("fetch"):
+        clock.advance(2.3)
+
+    assert len(timings.phases) == 1
+    phase = timings.phases[0]
+    assert phase.name == "fetch"
+    assert phase.seconds == 2.3
+    assert phase.spans == 1
+
+
+def test_spans_with_same_name_aggregate():
+    clock = FakeClock()
+    timings = TimingCollector(clock)
+
+    with timings.span("scan", unit="files") as span:
+        clock.advance(0.25)
+        span.add(nbytes=100)
+    with timings.span("scan", unit="files") as span:
+        clock.advance(0.5)
+        span.add(2, 300)
+
+    phase = timings.phases[0]
+    assert phase.seconds == 0.75
+    assert phase.count == 3
+    assert phase.bytes == 400
+    assert phase.spans == 2
+
+
+def test_nested_spans_tracked_separately():
+    clock = FakeClock()
+    timings = TimingCollector(clock)
+
+    with timings.span("load"):
+        clock.advance(1)
+        with timings.span("parse"):
+            clock.advance(2)
+
+    phases = {phase.name: phase for phase in timings.phases}
+    assert phases["load"].seconds == 3
+    assert phases["parse"].seconds == 2
+
+
+def test_span_recorded_on_exception():
+    clock = FakeClock()
+    timings = TimingCollector(clock)
+
+    try:
+        with timings.span("fetch"):
+            clock.advance(1)
+            raise RuntimeError("boom")
+    except RuntimeError:
+        pass
+
+    assert timings.phases[0].seconds == 1
+
+
+def test_spans_aggregate_across_threads():
+    timings = TimingCollector()
+
+    def work():
+        for _ in range(100):
+            with timings.span("parse") as span:
+                span.add(nbytes=10)
+
+    threads = [threading.Thread(target=work) for _ in range(4)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    phase = timings.phases[0]
+    assert phase.spans == 400
+    assert phase.count == 400
+    assert phase.bytes == 4000
+
+
+def test_format_duration():
+    assert format_duration(0.412) == "412ms"
+    assert format_duration(2.34) == "2.3s"
+
+
+def test_format_bytes():
+    assert format_bytes(512) == "512B"
+    assert format_bytes(2048) == "2KB"
+    assert format_bytes(48 * 1024 * 1024) == "48MB"
+
+
+def test_format_phase():
+    assert format_phase(Phase("fetch", seconds=2.3)) == "fetch 2.3s"
+    assert (
+        format_phase(
+            Phase("scan", "files", seconds=0.412, count=3214, bytes=48 * 1024 * 1024)
+        )
+        == "scan 412ms (3,214 files, 48MB)"
+    )
+
+
+def test_summary():
+    clock = FakeClock()
+    timings = TimingCollector(clock)
+
+    with timings.span("fetch"):
+        clock.advance(2.3)
+    with timings.span("scan", unit="files") as span:
+        clock.advance(0.412)
+        span.add(2, 2048)
+    with timings.span("build", unit="prompts") as span:
+        clock.advance(0.089)
+        span.add(2)
+
+    assert (
+        timings.summary()
+        == "fetch 2.3s, scan 412ms (2 files, 2KB), build 89ms (2 prompts)"
+    )